@@ -1,14 +1,32 @@
-
+#![allow(clippy::bool_comparison)]
 
 use chrono::{Weekday, NaiveDate};
 use std::convert::TryFrom;
 use inquire::{Confirm, Text, Editor, Password, DateSelect, Select, MultiSelect, PasswordDisplayMode};
+use inquire::list_option::ListOption;
+use inquire::ui::{RenderConfig, ErrorMessageRenderConfig, Styled, StyleSheet, Color, Attributes};
+use inquire::ui::calendar::CalendarRenderConfig;
+use inquire::error::{InquireError, InquireResult};
+use argon2::{Argon2, PasswordHasher};
+use argon2::password_hash::SaltString;
+use rand_core::OsRng;
+use regex::Regex;
 use clap::Parser;
 use std::path::Path;
+use std::fmt;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::net::{Ipv4Addr, Ipv6Addr, IpAddr};
 use std::io;
+use termion::{clear, style};
+use termion::raw::IntoRawMode;
+use termion::event::Key;
+use termion::input::TermRead;
+use std::os::unix::io::AsRawFd;
 use std::ffi::OsStr;
 use std::fs::{File, OpenOptions};
 use std::io::prelude::*;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 
 use linked_hash_map::LinkedHashMap;
 
@@ -27,14 +45,21 @@ struct Args {
 
     /// answer output file path
     #[clap(short, long)]
-    output_answers_file: Option<String>
+    output_answers_file: Option<String>,
+
+    /// show a summary of the answers to review and edit before writing them
+    #[clap(short, long)]
+    review: bool,
+
+    /// locale of the questionnaire and built-in messages, e.g. es or pt-BR, defaults to LANG
+    #[clap(short, long)]
+    lang: Option<String>
 }
 
 fn main() {
     let args = Args::parse();
-    let mut cfgs;
-    cfgs = if let Some(c) = &args.config{
-        let conf = YamlLoader::load_from_str(&c).unwrap();
+    let conf = if let Some(c) = &args.config{
+        let conf = YamlLoader::load_from_str(c).unwrap();
         conf[0].clone()
     } 
     // else if let Some(c) = &args.config_file{
@@ -46,38 +71,101 @@ fn main() {
     else {
         panic!("Must either be a config or config_file!");
     };
+
+    let langs = locale_candidates(&args);
+    set_translations(&langs, &conf["translations"]);
+    let conf = localize(&conf, &langs);
+
+    // either a list of questions or a questionnaire hash holding them under questions
+    let (questionnaire, cfgs) = if conf.as_hash().is_some() {
+        if conf["questions"].as_vec().is_none() {
+            panic!("questions attribute must be an array!");
+        }
+        (conf.clone(), conf["questions"].clone())
+    }else if conf.as_vec().is_some() {
+        (Yaml::Hash(LinkedHashMap::new()), conf)
+    }else{
+        panic!("config must be either an array of questions or a hash with questions!");
+    };
     
-    let mut answer_conf = read_answer_conf(&args);
+    let cfgs = inherit_render_config(&cfgs, &questionnaire["render_config"]);
+    check_output_policies(&cfgs);
+
+    let answer_conf = read_answer_conf(&args);
+
+    print_header(&questionnaire);
 
     // dbg!(&answer_conf);
 
-    let answer_array = parse_cli(&args, &cfgs, answer_conf.clone());
+    let mut answer_array = parse_cli(&args, &questionnaire, &cfgs, answer_conf.clone());
 
     // dbg!(&answer_array);
 
+    if args.review {
+        review(&args, &cfgs, &mut answer_array, &answer_conf);
+    }
+
     write_output(&args, &cfgs, answer_array, answer_conf);
 }
 
 #[derive(Debug)]
 struct Answer {
     name   : String,
-    answer : Vec<String>
+    answer : Yaml,
+    // the answer before its formatters, the default when the question is asked again
+    raw    : Yaml
+}
+
+// passwords apply their formatters themselves, before hashing, and groups apply
+// the formatters of their sub-questions to every item
+fn answered(cfg:&Yaml, raw:Yaml)-> Answer{
+    let answer = match cfg["type"].as_str() {
+        Some("password") => raw.clone(),
+        Some("group") => formatted_items(cfg, &raw),
+        _ => format_answer(cfg, "formatters", raw.clone())
+    };
+    Answer{ name : question_name(cfg).to_string(), answer, raw }
+}
+
+fn formatted_items(cfg:&Yaml, val:&Yaml)-> Yaml{
+    let formatted_item = |item:&Yaml| match item.as_hash() {
+        Some(x) => Yaml::Hash(x.iter().map(|(k, v)| {
+            let v = match k.as_str().and_then(|name| question_cfg(&cfg["questions"], name)) {
+                Some(q) => answered(q, v.clone()).answer,
+                None => v.clone()
+            };
+            (k.clone(), v)
+        }).collect()),
+        None => item.clone()
+    };
+    match val {
+        Yaml::Array(items) => Yaml::Array(items.iter().map(formatted_item).collect()),
+        _ => formatted_item(val)
+    }
+}
+
+fn string_array(answer:Vec<String>)-> Yaml{
+    let mut a = Vec::new();
+    for v in answer{
+        a.push(Yaml::String(v))
+    }
+    Yaml::Array(a)
 }
 
 fn read_answer_conf(args: &Args) -> Yaml {
-    let mut answer_conf;
+    let answer_conf;
     if let Some(output_answers_file) = args.output_answers_file.clone() {
         let path = Path::new(&output_answers_file);
         let output_answers_dir = path.parent().unwrap();
         if output_answers_dir.exists() && output_answers_dir.is_dir() {
             if path.exists() == false { //create the file
                 //set a empty yaml
-                File::create(output_answers_file);
+                File::create(output_answers_file).unwrap();
                 answer_conf = Yaml::Hash(LinkedHashMap::new());
             } else {
                 //read/parse the file to yaml
                 let file_content = std::fs::read_to_string(output_answers_file).unwrap();
-                if file_content.len() == 0 {
+                if file_content.is_empty() {
                     answer_conf = Yaml::Hash(LinkedHashMap::new())
                 }else{
                     answer_conf = YamlLoader::load_from_str(&file_content).unwrap()[0].clone()
@@ -92,348 +180,3130 @@ fn read_answer_conf(args: &Args) -> Yaml {
     answer_conf
 }
 
-fn parse_cli(args: &Args, cfgs: &Yaml , mut answer_conf: Yaml)->Vec<Option<Answer>> {
-
-    let mut result: Vec<Option<Answer>> = Vec::new();    
-    // dbg!(&cfgs);    
-    for cfg in cfgs.as_vec().unwrap() {
-        if let Some(output_answers_file) = args.output_answers_file.clone() {
-            let path = Path::new(&output_answers_file);
-            let output_answers_dir = path.parent().unwrap();
-            if output_answers_dir.exists() && output_answers_dir.is_dir() {
-                if path.exists() == false { //create the file
-                    //set a empty yaml
-                    File::create(output_answers_file);
-                    answer_conf = Yaml::Hash(LinkedHashMap::new());
-                } else {
-                    //read/parse the file to yaml
-                    let file_content = std::fs::read_to_string(output_answers_file).unwrap();
-                    if file_content.len() == 0 {
-                        answer_conf = Yaml::Hash(LinkedHashMap::new())
-                    }else{
-                        answer_conf = YamlLoader::load_from_str(&file_content).unwrap()[0].clone()
-                    };
-                };
-            } else {
-                panic!("Output answers {} base directory must exist ", output_answers_dir.to_string_lossy());
-            }
-        } else {
-            panic!("output_answers_file option must be defined.");
-        }
-        
-        let name = if  cfg["name"].is_badvalue() == false {
-            if let Some(name) = cfg["name"].as_str(){
-                name
-            }else{
-                panic!("Could not get name attribute value.")
-            }
-        }else{
-            panic!("A name is required for the question to tie this answer to an attribute.");
-        };
-        
-        if cfg["type"].is_badvalue() == false {
-            if let Some(t) = cfg["type"].as_str(){
-                
-                result.push(Some(
-                    Answer{
-                        answer : match t {
-                            "confirm"      => confirm(&args, cfg),
-                            "text"         => text(&args, cfg),
-                            "editor"       => editor(&args, cfg),
-                            "password"     => password(&args, cfg),
-                            "date_select"  => date_select(&args,cfg),
-                            "select"       => select(&args,cfg),
-                            "multi_select" => multi_select(&args,cfg),
-                            _              => panic!("Unknown type \"{}\" must be either text, editor, date_select, select, multi_select, confirm, password !", t),
-                        },
-                        name : name.to_string()
-                    }
-
-                ));
+// copies the questionnaire render_config into every question, group questions
+// included, the question's own settings taking precedence
+fn inherit_render_config(questions:&Yaml, base:&Yaml)-> Yaml{
+    let mut updated = Vec::new();
+    for q in questions.as_vec().unwrap() {
+        let mut question = q.as_hash().expect("Each question must be a hash!").clone();
 
-                // dbg!(&result);
-                let mut res = Vec::new();
-                for rz in &result {
-                    if let Some(results) = rz{
-                        for r in &results.answer{
-                            res.push(Yaml::String(r.clone()));
-                        }
-                    }
+        let mut merged = match base.as_hash() {
+            Some(x) => x.clone(),
+            None => LinkedHashMap::new()
+        };
+        if q["render_config"].is_badvalue() == false {
+            if let Some(x) = q["render_config"].as_hash(){
+                for (k, v) in x {
+                    merged.insert(k.clone(), v.clone());
                 }
-
             }else{
-                panic!("type attribute must be a string!");
-            };
-        }else{
-            panic!("type attribute must be defined!");
+                panic!("render_config attribute must be a hash!");
+            }
         }
-        
+        let merged = Yaml::Hash(merged);
+
+        if let Some(sub) = q["questions"].as_vec() {
+            question.insert(Yaml::String("questions".to_string()), inherit_render_config(&Yaml::Array(sub.clone()), &merged));
         }
-    return result;
+        question.insert(Yaml::String("render_config".to_string()), merged);
+        updated.push(Yaml::Hash(question));
+    }
+    Yaml::Array(updated)
 }
 
-// if the answer_conf has a hash key that the answer list does not have then add it 
-fn write_output(args:&Args, cfg:&Yaml, answer_list:Vec<Option<Answer>>, answer_conf:Yaml){
-    let mut output_conf = answer_conf.clone();
+// the locales to look for, most specific first: --lang or LANG "pt_BR.UTF-8"
+// gives pt-br, pt and then en
+fn locale_candidates(args:&Args)-> Vec<String>{
+    let lang = match &args.lang {
+        Some(x) => x.clone(),
+        None => std::env::var("LANG").unwrap_or_default()
+    };
+    let lang = lang.split(['.', '@']).next().unwrap().replace('_', "-").to_lowercase();
 
-    for some_answer in answer_list {
-        if let Some(answer) = some_answer {
-            output_conf = if let Yaml::Hash(mut x) = output_conf.clone() {
-                let mut a = Vec::new();
-                for v in answer.answer{
-                    a.push(Yaml::String(v))
-                }
-                x.insert(Yaml::String(answer.name.clone()), Yaml::Array(a));
-                Yaml::Hash(x)
-            }else{
-                panic!("Should be unreachable");
-            };
+    let mut langs = Vec::new();
+    if lang.is_empty() == false && lang != "c" && lang != "posix" {
+        langs.push(lang.clone());
+        if let Some((language, _)) = lang.split_once('-') {
+            langs.push(language.to_string());
         }
     }
-    
-    let mut out_str = String::new();
-    let mut emitter = YamlEmitter::new(&mut out_str);
-    emitter.dump(&output_conf).unwrap(); // dump the YAML object to a String
-
-    let mut file = OpenOptions::new()
-        .read(false)
-        .write(true)
-        .create(false)
-        .truncate(true)
-        .open(args.output_answers_file.clone().unwrap())
-        .unwrap();
-    
-    
-    file.write_all(out_str.as_bytes()).unwrap();
-    file.sync_all();
-    // dbg!(output_conf);
-    // dbg!(out_str);
-
+    if langs.contains(&"en".to_string()) == false {
+        langs.push("en".to_string());
+    }
+    langs
 }
 
-fn confirm(args:&Args, cfg:&Yaml)-> Vec<String>{
-    let mut inq = if let Some(msg) = cfg["message"].as_str(){
-        Confirm::new(msg)
-    }else {
-        panic!("Error: A message is required to inquired.");
-    };
-
+fn normalize_locale(locale:&str)-> String{
+    locale.replace('_', "-").to_lowercase()
+}
 
-    if cfg["help"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["help"].as_str(){
-            inq.with_help_message(x)
-        }else{
-            panic!("help attribute must be a string!");
-        };
+// replaces the locale maps of the text attributes, e.g. "message": {"en": "Name?", "es": "¿Nombre?"},
+// by the best match for the locale, falling back to en and then to the first entry
+fn localize(val:&Yaml, langs:&[String])-> Yaml{
+    const LOCALIZED: [&str; 10] = ["message", "help", "placeholder", "title", "description", "section", "add_message", "confirm_message", "label", "group"];
+    match val {
+        Yaml::Hash(x) => Yaml::Hash(x.iter().map(|(k, v)| {
+            let key = k.as_str().unwrap_or("");
+            let v = match v {
+                Yaml::Hash(locales) if LOCALIZED.contains(&key) => {
+                    let found = langs.iter().find_map(|l| {
+                        locales.iter().find(|(locale, _)| locale.as_str().map(normalize_locale).as_ref() == Some(l))
+                    });
+                    match found.or_else(|| locales.iter().next()) {
+                        Some((_, text)) => text.clone(),
+                        None => panic!("{} attribute must not be an empty locale map!", key)
+                    }
+                },
+                // translations are keyed by english text and render_config styles share key names
+                _ if key == "translations" || key == "render_config" => v.clone(),
+                _ => localize(v, langs)
+            };
+            (k.clone(), v)
+        }).collect()),
+        Yaml::Array(x) => Yaml::Array(x.iter().map(|v| localize(v, langs)).collect()),
+        _ => val.clone()
     }
+}
 
-    if cfg["default"].is_badvalue() == false {
-        inq = if let Some(default) = cfg["default"].as_bool(){
-            inq.with_default(default)
-        }else{
-            panic!("default attribute must be a boolean!");
-        };
+// the built-in messages in the selected locale, the questionnaire "translations"
+// hash, keyed by locale then by the english message, adds to or overrides them
+static TRANSLATIONS: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+fn set_translations(langs:&[String], custom:&Yaml){
+    let mut table = HashMap::new();
+    for lang in langs.iter().rev() {
+        for (en, translated) in builtin_translations(lang) {
+            table.insert(en.to_string(), translated.to_string());
+        }
     }
 
-    if cfg["placeholder"].is_badvalue() == false {
-        inq = if let Some(placeholder) = cfg["placeholder"].as_str(){
-            inq.with_placeholder(placeholder)
+    if custom.is_badvalue() == false {
+        let locales = if let Some(x) = custom.as_hash(){
+            x
         }else{
-            panic!("placeholder attribute must be a string!");
+            panic!("translations attribute must be a hash!");
         };
-    }
-
-    if cfg["skippable"].is_badvalue() == false {
-        if let Some(x) = cfg["skippable"].as_bool(){
-            if x {
-                if let Some(r) = inq.prompt_skippable().unwrap(){
-                    let val = if r {
-                        "true"
-                    } else {
-                        "false"
-                    };
-                    vec![val.to_string()]
-                }else{
-                    Vec::new()
+        for lang in langs.iter().rev() {
+            for (locale, messages) in locales {
+                if locale.as_str().map(normalize_locale).as_ref() != Some(lang) {
+                    continue;
                 }
-            }else{
-                let val = if inq.prompt().unwrap() {
-                    "true"
-                } else {
-                    "false"
+                let messages = if let Some(x) = messages.as_hash(){
+                    x
+                }else{
+                    panic!("translations must be hashes of english message to translated message!");
                 };
-                vec![val.to_string()]
+                for (en, translated) in messages {
+                    match (en.as_str(), translated.as_str()) {
+                        (Some(e), Some(t)) => table.insert(e.to_string(), t.to_string()),
+                        _ => panic!("translations must be hashes of english message to translated message!")
+                    };
+                }
             }
-        }else{
-            panic!("skippable attribute must be a bool!");
         }
-    }else{
-        let val = if inq.prompt().unwrap() {
-            "true"
-        } else {
-            "false"
-        };
-        vec![val.to_string()]
     }
+    TRANSLATIONS.set(table).unwrap();
 }
 
-
-fn text(args:&Args, cfg:&Yaml)-> Vec<String>{
-    let mut inq = if let Some(msg) = cfg["message"].as_str(){
-        Text::new(msg)
-    }else {
-        panic!("Error: A message is required to inquired.");
+// a built-in message in the selected locale, each {} is replaced by the next argument
+fn tr(msg:&str, args:&[&dyn fmt::Display])-> String{
+    let template = match TRANSLATIONS.get().and_then(|t| t.get(msg)) {
+        Some(x) => x.as_str(),
+        None => msg
     };
+    let mut out = String::new();
+    let mut rest = template;
+    let mut args = args.iter();
+    while let Some(i) = rest.find("{}") {
+        out.push_str(&rest[..i]);
+        if let Some(a) = args.next() {
+            out.push_str(&a.to_string());
+        }
+        rest = &rest[i + 2..];
+    }
+    out.push_str(rest);
+    out
+}
 
-    if cfg["help"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["help"].as_str(){
-            inq.with_help_message(x)
+// the RenderConfig built for every distinct render_config, inquire keeps custom
+// glyphs as &'static str so each one is leaked once per run, not once per prompt
+static RENDER_CONFIGS: OnceLock<Mutex<HashMap<Yaml, RenderConfig>>> = OnceLock::new();
+
+fn render_config(cfg:&Yaml)-> RenderConfig{
+    let rc = &cfg["render_config"];
+    let mut built = RENDER_CONFIGS.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+    *built.entry(rc.clone()).or_insert_with(|| build_render_config(rc))
+}
+
+// builds the inquire RenderConfig of a question from its theme and overrides,
+// colors are left out when NO_COLOR is set
+fn build_render_config(rc:&Yaml)-> RenderConfig{
+    let color = std::env::var("NO_COLOR").is_err();
+
+    let theme = if rc["theme"].is_badvalue() == false {
+        if let Some(x) = rc["theme"].as_str(){
+            x
         }else{
-            panic!("help attribute must be a string!");
-        };
+            panic!("render_config theme attribute must be a string!");
+        }
+    }else{
+        "default"
+    };
+
+    let mut config = match theme {
+        "default" => RenderConfig::default_colored(),
+        "plain"   => RenderConfig::empty(),
+        "minimal" => RenderConfig::empty()
+            .with_prompt_prefix(Styled::new(">"))
+            .with_highlighted_option_prefix(Styled::new("-"))
+            .with_selected_checkbox(Styled::new("(*)"))
+            .with_unselected_checkbox(Styled::new("( )")),
+        "vivid"   => RenderConfig::default_colored()
+            .with_prompt_prefix(Styled::new("❯").with_fg(Color::LightMagenta))
+            .with_highlighted_option_prefix(Styled::new("➤").with_fg(Color::LightMagenta))
+            .with_selected_checkbox(Styled::new("◉").with_fg(Color::LightGreen))
+            .with_unselected_checkbox(Styled::new("○"))
+            .with_help_message(StyleSheet::new().with_fg(Color::DarkYellow).with_attr(Attributes::ITALIC))
+            .with_answer(StyleSheet::new().with_fg(Color::LightMagenta).with_attr(Attributes::BOLD)),
+        _ => panic!("unknown theme {} must be either default, plain, minimal, vivid", theme)
+    };
+    if color == false {
+        config = plain_colors(config);
     }
+    let canceled: &'static str = Box::leak(tr("<canceled>", &[]).into_boxed_str());
+    config.canceled_prompt_indicator.content = canceled;
 
-    if cfg["default"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["default"].as_str(){
-            inq.with_default(x)
-        }else{
-            panic!("default attribute must be a string!");
-        };
+    if rc.as_hash().is_none() {
+        return config;
     }
 
-    if cfg["page_size"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["page_size"].as_i64(){
-            inq.with_page_size(x.try_into().unwrap())
-        }else{
-            panic!("help attribute must be a string!");
+    if rc["prompt_prefix"].is_badvalue() == false {
+        config.prompt_prefix = styled(&rc["prompt_prefix"], config.prompt_prefix, color);
+    }
+    if rc["highlighted_option_prefix"].is_badvalue() == false {
+        config.highlighted_option_prefix = styled(&rc["highlighted_option_prefix"], config.highlighted_option_prefix, color);
+    }
+    if rc["selected_checkbox"].is_badvalue() == false {
+        config.selected_checkbox = styled(&rc["selected_checkbox"], config.selected_checkbox, color);
+    }
+    if rc["unselected_checkbox"].is_badvalue() == false {
+        config.unselected_checkbox = styled(&rc["unselected_checkbox"], config.unselected_checkbox, color);
+    }
+    if rc["scroll_up_prefix"].is_badvalue() == false {
+        config.scroll_up_prefix = styled(&rc["scroll_up_prefix"], config.scroll_up_prefix, color);
+    }
+    if rc["scroll_down_prefix"].is_badvalue() == false {
+        config.scroll_down_prefix = styled(&rc["scroll_down_prefix"], config.scroll_down_prefix, color);
+    }
+    if rc["canceled_prompt_indicator"].is_badvalue() == false {
+        config.canceled_prompt_indicator = styled(&rc["canceled_prompt_indicator"], config.canceled_prompt_indicator, color);
+    }
+    if rc["error_prefix"].is_badvalue() == false {
+        config.error_message.prefix = styled(&rc["error_prefix"], config.error_message.prefix, color);
+    }
+    if rc["prompt"].is_badvalue() == false {
+        config.prompt = style_sheet(&rc["prompt"], color);
+    }
+    if rc["help_message"].is_badvalue() == false {
+        config.help_message = style_sheet(&rc["help_message"], color);
+    }
+    if rc["answer"].is_badvalue() == false {
+        config.answer = style_sheet(&rc["answer"], color);
+    }
+    if rc["default_value"].is_badvalue() == false {
+        config.default_value = style_sheet(&rc["default_value"], color);
+    }
+    if rc["placeholder"].is_badvalue() == false {
+        config.placeholder = style_sheet(&rc["placeholder"], color);
+    }
+    if rc["text_input"].is_badvalue() == false {
+        config.text_input = style_sheet(&rc["text_input"], color);
+    }
+    if rc["option"].is_badvalue() == false {
+        config.option = style_sheet(&rc["option"], color);
+    }
+    if rc["editor_prompt"].is_badvalue() == false {
+        config.editor_prompt = style_sheet(&rc["editor_prompt"], color);
+    }
+    if rc["error_message"].is_badvalue() == false {
+        config.error_message.message = style_sheet(&rc["error_message"], color);
+    }
+    if rc["password_mask"].is_badvalue() == false {
+        config.password_mask = match rc["password_mask"].as_str().map(|x| x.chars().collect::<Vec<char>>()) {
+            Some(c) if c.len() == 1 => c[0],
+            _ => panic!("render_config password_mask attribute must be a single character!")
         };
     }
+    config
+}
 
-    if cfg["initial_value"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["initial_value"].as_str(){
-            inq.with_initial_value(x)
-        }else{
-            panic!("help attribute must be a string!");
-        };
+// the theme glyphs without any color, attributes such as bold are kept
+fn plain_colors(config:RenderConfig)-> RenderConfig{
+    let strip = |s:StyleSheet| StyleSheet::new().with_attr(s.att);
+    let strip_styled = |s:Styled<&'static str>| Styled::new(s.content).with_attr(s.style.att);
+    RenderConfig{
+        prompt_prefix: strip_styled(config.prompt_prefix),
+        prompt: strip(config.prompt),
+        default_value: strip(config.default_value),
+        placeholder: strip(config.placeholder),
+        help_message: strip(config.help_message),
+        text_input: strip(config.text_input),
+        answer: strip(config.answer),
+        canceled_prompt_indicator: strip_styled(config.canceled_prompt_indicator),
+        highlighted_option_prefix: strip_styled(config.highlighted_option_prefix),
+        scroll_up_prefix: strip_styled(config.scroll_up_prefix),
+        scroll_down_prefix: strip_styled(config.scroll_down_prefix),
+        selected_checkbox: strip_styled(config.selected_checkbox),
+        unselected_checkbox: strip_styled(config.unselected_checkbox),
+        option: strip(config.option),
+        editor_prompt: strip(config.editor_prompt),
+        error_message: ErrorMessageRenderConfig::empty(),
+        calendar: CalendarRenderConfig::empty(),
+        ..config
     }
-    let sub;
-    if cfg["suggestions"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["suggestions"].as_vec(){
-            
-            let mut suggestions = Vec::new();
-            for a in x {
-                let z = String::from(a.as_str().unwrap());
-                suggestions.push(z);
-            }            
+}
 
-            sub = move |val :&str| {
-                let sugg = suggestions.clone();
-                let val_lower = val.to_lowercase();
-                sugg
-                    .iter()
-                    .filter(|s| s.to_lowercase().contains(&val_lower))
-                    .map(|s| String::from(s))
-                    .collect()
-            };
-            inq.with_suggester(&sub)
-        }else{
-            panic!("help attribute must be a string!");
-        };
+// text in a render_config style, for the prompts drawn here with termion
+fn painted<T:fmt::Display>(val:&Styled<T>)-> String{
+    let mut out = String::new();
+    if let Some(c) = val.style.fg {
+        out.push_str(&termion::color::Fg(c).to_string());
+    }
+    if let Some(c) = val.style.bg {
+        out.push_str(&termion::color::Bg(c).to_string());
+    }
+    if val.style.att.contains(Attributes::BOLD) {
+        out.push_str(style::Bold.as_ref());
+    }
+    if val.style.att.contains(Attributes::ITALIC) {
+        out.push_str(style::Italic.as_ref());
+    }
+    out.push_str(&val.content.to_string());
+    if val.style.fg.is_some() {
+        out.push_str(&termion::color::Fg(termion::color::Reset).to_string());
     }
+    if val.style.bg.is_some() {
+        out.push_str(&termion::color::Bg(termion::color::Reset).to_string());
+    }
+    if val.style.att.is_empty() == false {
+        out.push_str(style::Reset.as_ref());
+    }
+    out
+}
 
-    if cfg["placeholder"].is_badvalue() == false {
-        inq = if let Some(placeholder) = cfg["placeholder"].as_str(){
-            inq.with_placeholder(placeholder)
-        }else{
-            panic!("placeholder attribute must be a string!");
-        };
+// a glyph either as a plain string or as {content, fg, bg, bold, italic}
+fn styled(val:&Yaml, current:Styled<&'static str>, color:bool)-> Styled<&'static str>{
+    if let Some(x) = val.as_str() {
+        let content: &'static str = Box::leak(x.to_string().into_boxed_str());
+        return Styled::new(content).with_style_sheet(current.style);
+    }
+    if val.as_hash().is_none() {
+        panic!("render_config glyphs must be a string or a hash!");
     }
+    let content: &'static str = match val["content"].as_str() {
+        Some(x) => Box::leak(x.to_string().into_boxed_str()),
+        None => current.content
+    };
+    Styled::new(content).with_style_sheet(style_sheet(val, color))
+}
 
-    if cfg["skippable"].is_badvalue() == false {
-        if let Some(x) = cfg["skippable"].as_bool(){
-            if x {
-                if let Some(r) = inq.prompt_skippable().unwrap(){
-                    vec![r]
-                }else{
-                    Vec::new()
-                }
+fn style_sheet(val:&Yaml, color:bool)-> StyleSheet{
+    if val.as_hash().is_none() {
+        panic!("render_config styles must be a hash with fg, bg, bold, italic!");
+    }
+    let mut sheet = StyleSheet::new();
+    if color {
+        if val["fg"].is_badvalue() == false {
+            sheet = sheet.with_fg(parse_color(&val["fg"]));
+        }
+        if val["bg"].is_badvalue() == false {
+            sheet = sheet.with_bg(parse_color(&val["bg"]));
+        }
+    }
+    if val["bold"].as_bool() == Some(true) {
+        sheet = sheet.with_attr(Attributes::BOLD);
+    }
+    if val["italic"].as_bool() == Some(true) {
+        sheet = sheet.with_attr(sheet.att | Attributes::ITALIC);
+    }
+    sheet
+}
+
+// a color name or an ansi 0-255 value
+fn parse_color(val:&Yaml)-> Color{
+    if let Some(x) = val.as_i64() {
+        return Color::AnsiValue(u8::try_from(x).expect("ansi color values must be between 0 and 255!"));
+    }
+    match val.as_str() {
+        Some("black")        => Color::Black,
+        Some("red")          => Color::LightRed,
+        Some("dark_red")     => Color::DarkRed,
+        Some("green")        => Color::LightGreen,
+        Some("dark_green")   => Color::DarkGreen,
+        Some("yellow")       => Color::LightYellow,
+        Some("dark_yellow")  => Color::DarkYellow,
+        Some("blue")         => Color::LightBlue,
+        Some("dark_blue")    => Color::DarkBlue,
+        Some("magenta")      => Color::LightMagenta,
+        Some("dark_magenta") => Color::DarkMagenta,
+        Some("cyan")         => Color::LightCyan,
+        Some("dark_cyan")    => Color::DarkCyan,
+        Some("white")        => Color::White,
+        Some("grey")         => Color::Grey,
+        Some("dark_grey")    => Color::DarkGrey,
+        _ => panic!("unknown color {:?} must be either black, red, dark_red, green, dark_green, yellow, dark_yellow, blue, dark_blue, magenta, dark_magenta, cyan, dark_cyan, white, grey, dark_grey or 0-255", val)
+    }
+}
+
+// the questionnaire title and description, shown before the first prompt
+fn print_header(questionnaire:&Yaml){
+    let color = std::env::var("NO_COLOR").is_err();
+    if questionnaire["title"].is_badvalue() == false {
+        if let Some(x) = questionnaire["title"].as_str(){
+            if color {
+                println!("{}{}{}", style::Bold, x, style::Reset);
             }else{
-                vec![inq.prompt().unwrap()]
+                println!("{}", x);
             }
         }else{
-            panic!("skippable attribute must be a bool!");
+            panic!("title attribute must be a string!");
         }
-    }else{
-        vec![inq.prompt().unwrap()]
     }
-
+    if questionnaire["description"].is_badvalue() == false {
+        if let Some(x) = questionnaire["description"].as_str(){
+            println!("{}", x);
+        }else{
+            panic!("description attribute must be a string!");
+        }
+    }
+    if questionnaire["title"].is_badvalue() == false || questionnaire["description"].is_badvalue() == false {
+        println!();
+    }
 }
 
+fn parse_cli(args: &Args, questionnaire: &Yaml, cfgs: &Yaml , answer_conf: Yaml)->Vec<Option<Answer>> {
 
-fn password(args:&Args, cfg:&Yaml)-> Vec<String>{
-    let mut inq = if let Some(msg) = cfg["message"].as_str(){
-        Password::new(msg)
-    }else {
-        panic!("Error: A message is required to inquired.");
+    let questions = cfgs.as_vec().unwrap();
+    let mut result: Vec<Option<Answer>> = Vec::new();    
+    // the last answer of every question, used as its default after going back
+    let mut previous: Vec<Option<Yaml>> = questions.iter().map(|q| existing_entries(q, &answer_conf)).collect();
+    let mut timed_out: Vec<String> = Vec::new();
+    // dbg!(&cfgs);    
+    while result.len() < questions.len() {
+        let index = result.len();
+        let cfg = &questions[index];
+        let name = question_name(cfg);
+
+        let scope = answers_scope(&result);
+        if condition_met(cfg, &scope) == false {
+            result.push(None);
+            continue;
+        }
+
+        let mut rendered = render_cfg(cfg, &scope, &answer_conf);
+        if let Some(p) = &previous[index] {
+            rendered = with_previous_answer(&rendered, p);
+        }
+        rendered = with_progress(questionnaire, questions, &result, &rendered);
+
+        if let Some(answer) = timeout_answer(&rendered) {
+            timed_out.retain(|n| n != name);
+            timed_out.push(name.to_string());
+            previous[index] = Some(answer.clone());
+            result.push(Some(answered(cfg, answer)));
+            continue;
+        }
+
+        match ask(args, &rendered, &answer_conf, &scope) {
+            Ok(answer) => {
+                previous[index] = answer.clone();
+                result.push(answer.map(|answer| answered(cfg, answer)));
+            },
+            Err(GoBack) => {
+                // back to the last question the user actually answered, the ones in
+                // between are asked again so their when conditions are re-evaluated
+                let back_to = (0..index).rev().find(|&i| {
+                    result[i].is_some() && questions[i]["type"].as_str() != Some("computed")
+                });
+                if let Some(i) = back_to {
+                    result.truncate(i);
+                }
+            }
+        }
+    }
+
+    // only the timeouts that still hold after going back are reported
+    timed_out.retain(|n| result.iter().flatten().any(|a| &a.name == n));
+    if timed_out.is_empty() == false {
+        println!("{}", tr("Timed out, the default was used for: {}", &[&timed_out.join(", ")]));
+    }
+    result
+}
+
+// for questions with a timeout, waits for a key press while counting down and
+// returns the default answer when nothing was typed in time
+fn timeout_answer(cfg:&Yaml)-> Option<Yaml>{
+    if cfg["timeout"].is_badvalue() {
+        return None;
+    }
+    let timeout = if let Some(x) = cfg["timeout"].as_i64(){
+        u64::try_from(x).unwrap()
+    }else{
+        panic!("timeout attribute must be a i64!");
+    };
+    let message = cfg["message"].as_str().unwrap_or("");
+
+    if wait_for_key(message, timeout) {
+        return None;
+    }
+
+    let answer = match default_answer(cfg) {
+        Some(Ok(x)) => x,
+        Some(Err(e)) => panic!("Question {} timed out and its default is not valid: {}", question_name(cfg), e),
+        None => panic!("Question {} timed out and has no default!", question_name(cfg))
+    };
+    let shown = format_answer(cfg, "display_formatters", answered(cfg, answer.clone()).answer);
+    println!("? {} {} {}", message, display_answer(&shown), tr("(timed out)", &[]));
+    Some(answer)
+}
+
+// true as soon as a key is pressed, the key is left for the prompt to read
+fn wait_for_key(message:&str, timeout:u64)-> bool{
+    let tty = match OpenOptions::new().read(true).write(true).open("/dev/tty") {
+        Ok(t) => t,
+        Err(_) => return true
+    };
+    let fd = tty.as_raw_fd();
+    let mut out = tty.into_raw_mode().unwrap();
+    let color = std::env::var("NO_COLOR").is_err();
+
+    for remaining in (1..=timeout).rev() {
+        let help = tr("[using the default in {}s, press any key to answer]", &[&remaining]);
+        if color {
+            write!(out, "\r{}? {} {}{}{}", clear::CurrentLine, message, style::Faint, help, style::Reset).unwrap();
+        }else{
+            write!(out, "\r{}? {} {}", clear::CurrentLine, message, help).unwrap();
+        }
+        out.flush().unwrap();
+
+        let mut fds = libc::pollfd{ fd, events: libc::POLLIN, revents: 0 };
+        if unsafe { libc::poll(&mut fds, 1, 1000) } > 0 {
+            write!(out, "\r{}", clear::CurrentLine).unwrap();
+            return true;
+        }
+    }
+    write!(out, "\r{}", clear::CurrentLine).unwrap();
+    false
+}
+
+// the answer a question gives when accepting its default, None when it has none.
+// it goes through the same validators and post-processing as a typed answer
+fn default_answer(cfg:&Yaml)-> Option<Result<Yaml, String>>{
+    let choices = option_choices(cfg);
+    let options: Vec<String> = choices.iter().map(|o| o.value.clone()).collect();
+    // disabled options are never part of a default
+    let enabled = |i:&i64| choices.get(usize::try_from(*i).unwrap()).filter(|o| o.disabled.is_none()).map(|o| o.value.clone());
+    let default = &cfg["default"];
+
+    let validated = |val:&str| validate_string(cfg, val).map(|_| string_array(vec![val.to_string()]));
+
+    let answer = match cfg["type"].as_str().unwrap_or("") {
+        "text" => return default.as_str().map(|d| validate_mask(cfg, d).and_then(|_| validated(d))),
+        "multiline" => return cfg["predefined_text"].as_str().map(validated),
+        "path" => return default.as_str().map(|d| {
+            let rules = path_rules(cfg);
+            validate_path(d, &rules).map(|_| string_array(vec![path_answer(&rules, d)]))
+        }),
+        // an editor left untouched answers its predefined text, or an empty one
+        "editor" => {
+            let text = editor_predefined_text(cfg).unwrap_or_default();
+            return Some(editor_answer(cfg, &editor_rules(cfg), &text));
+        },
+        "list" => return default.as_vec().map(|_| {
+            let items = answer_strings(default);
+            for item in &items {
+                validate_string(cfg, item)?;
+            }
+            Ok(string_array(items))
+        }),
+        "date_select" => default.as_str().map(|d| string_array(vec![d.to_string()])),
+        "confirm" => default.as_bool().map(|d| string_array(vec![d.to_string()])),
+        "select" => cfg["starting_cursor"].as_i64()
+            .and_then(|i| enabled(&i))
+            .map(|o| string_array(vec![o])),
+        "multi_select" => match default {
+            Yaml::BadValue => None,
+            _ => Some(string_array(multi_select_defaults(cfg, &choices).into_iter().map(|i| choices[i].value.clone()).collect()))
+        },
+        "rank" => default.as_vec().map(|_| {
+            let mut order = answer_strings(default);
+            for o in &options {
+                if order.contains(o) == false {
+                    order.push(o.clone());
+                }
+            }
+            if let Some(limit) = cfg["limit"].as_i64() {
+                order.truncate(usize::try_from(limit).unwrap());
+            }
+            string_array(order)
+        }),
+        _ => None
+    };
+    answer.map(Ok)
+}
+
+// prefixes the message with the question section and, when the questionnaire
+// has progress enabled, "Question n of total"
+fn with_progress(questionnaire:&Yaml, questions:&[Yaml], result:&[Option<Answer>], cfg:&Yaml)-> Yaml{
+    let t = cfg["type"].as_str();
+    if t == Some("note") || t == Some("computed") || cfg["message"].as_str().is_none() {
+        return cfg.clone();
+    }
+
+    let progress = if questionnaire["progress"].is_badvalue() == false {
+        if let Some(x) = questionnaire["progress"].as_bool(){
+            x
+        }else{
+            panic!("progress attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    let mut prefix = Vec::new();
+    if cfg["section"].is_badvalue() == false {
+        if let Some(x) = cfg["section"].as_str(){
+            prefix.push(x.to_string());
+        }else{
+            panic!("section attribute must be a string!");
+        }
+    }
+
+    if progress {
+        let prompted_question = |q:&Yaml| {
+            let t = q["type"].as_str();
+            t != Some("note") && t != Some("computed")
+        };
+        let index = result.len();
+        let answered = (0..index).filter(|&i| result[i].is_some() && prompted_question(&questions[i])).count();
+
+        // later questions count unless their when is already known to be false
+        let scope = answers_scope(result);
+        let remaining = questions[index + 1..].iter().filter(|q| {
+            if prompted_question(q) == false {
+                return false;
+            }
+            match q["when"].as_str() {
+                Some(w) if condition_names(w).iter().all(|n| scope[n.as_str()].is_badvalue() == false) => eval_condition(w, &scope),
+                _ => true
+            }
+        }).count();
+
+        prefix.push(tr("Question {} of {}", &[&(answered + 1), &(answered + 1 + remaining)]));
+    }
+
+    if prefix.is_empty() {
+        return cfg.clone();
+    }
+
+    let mut updated = cfg.as_hash().unwrap().clone();
+    let message = format!("{}: {}", prefix.join(" - "), cfg["message"].as_str().unwrap());
+    updated.insert(Yaml::String("message".to_string()), Yaml::String(message));
+    Yaml::Hash(updated)
+}
+
+// a map starts from its entries in the answers file, existing is the hash holding
+// the question answer: the answers file or, inside a group, the item
+fn existing_entries(cfg:&Yaml, existing:&Yaml)-> Option<Yaml>{
+    if cfg["type"].as_str() != Some("map") {
+        return None;
+    }
+    let entries = &existing[question_name(cfg)];
+    entries.as_hash().map(|_| entries.clone())
+}
+
+// esc on a prompt that can not be skipped steps back: the question functions return
+// GoBack so composite questions can go back to their previous step, and when they
+// have none it reaches parse_cli which goes back to the previous question
+struct GoBack;
+
+fn prompted<T>(answer:InquireResult<T>)-> Result<T, GoBack>{
+    match answer {
+        Err(InquireError::OperationCanceled) => Err(GoBack),
+        _ => Ok(answer.unwrap())
+    }
+}
+
+fn question_name(cfg:&Yaml)-> &str{
+    // notes do not answer anything so they do not need a name
+    if cfg["type"].as_str() == Some("note") {
+        return cfg["name"].as_str().unwrap_or("");
+    }
+    if  cfg["name"].is_badvalue() == false {
+        if let Some(name) = cfg["name"].as_str(){
+            name
+        }else{
+            panic!("Could not get name attribute value.")
+        }
+    }else{
+        panic!("A name is required for the question to tie this answer to an attribute.");
+    }
+}
+
+// the answer of a question before its formatters, None for steps that do not answer anything
+fn ask(args: &Args, cfg: &Yaml, answer_conf: &Yaml, scope: &Yaml)-> Result<Option<Yaml>, GoBack>{
+    if cfg["type"].is_badvalue() == false {
+        if let Some(t) = cfg["type"].as_str(){
+            inquire::set_global_render_config(render_config(cfg));
+            if t == "note" {
+                note(args, cfg);
+                return Ok(None);
+            }
+            let answer = match t {
+                "confirm"      => string_array(confirm(args, cfg)?),
+                "text"         => string_array(text(args, cfg)?),
+                "editor"       => editor(args, cfg)?,
+                "multiline"    => string_array(multiline(args, cfg)),
+                "password"     => string_array(password(args, cfg)?),
+                "date_select"  => string_array(date_select(args,cfg)?),
+                "select"       => string_array(select(args,cfg)?),
+                "multi_select" => string_array(multi_select(args,cfg)?),
+                "rank"         => string_array(rank(args, cfg)?),
+                "path"         => string_array(path(args, cfg)?),
+                "list"         => string_array(list(args, cfg)?),
+                "map"          => map(args, cfg)?,
+                "group"        => group(args, cfg, answer_conf, scope)?,
+                "computed"     => string_array(computed(args, cfg, answer_conf, scope)),
+                _              => panic!("{}", tr("Unknown type \"{}\" must be either text, path, list, map, group, multiline, editor, date_select, select, multi_select, rank, confirm, password, note, computed !", &[&t])),
+            };
+            Ok(Some(answer))
+        }else{
+            panic!("type attribute must be a string!");
+        }
+    }else{
+        panic!("type attribute must be defined!");
+    }
+}
+
+// the answers collected so far keyed by question name, used by when and templates
+fn answers_scope(result:&[Option<Answer>])-> Yaml{
+    let mut scope = LinkedHashMap::new();
+    for answer in result.iter().flatten() {
+        scope.insert(Yaml::String(answer.name.clone()), answer.answer.clone());
+    }
+    Yaml::Hash(scope)
+}
+
+// a copy of the question with its text attributes rendered as templates
+fn render_cfg(cfg:&Yaml, scope:&Yaml, answer_conf:&Yaml)-> Yaml{
+    let mut rendered = cfg.as_hash().expect("Each question must be a hash!").clone();
+    for attribute in ["message", "help", "default", "placeholder", "initial_value", "predefined_text", "confirm_message", "title"] {
+        if let Some(x) = cfg[attribute].as_str() {
+            rendered.insert(Yaml::String(attribute.to_string()), Yaml::String(render_template(x, scope, answer_conf)));
+        }
+    }
+    // an option disabled by a when style expression is resolved against the answers so far
+    if let Some(options) = cfg["options"].as_vec() {
+        let resolved = options.iter().map(|o| match o["disabled"].as_str() {
+            Some(expr) => {
+                let mut option = o.as_hash().unwrap().clone();
+                option.insert(Yaml::String("disabled".to_string()), Yaml::Boolean(eval_condition(expr, scope)));
+                Yaml::Hash(option)
+            },
+            None => o.clone()
+        }).collect();
+        rendered.insert(Yaml::String("options".to_string()), Yaml::Array(resolved));
+    }
+    Yaml::Hash(rendered)
+}
+
+// replaces every {{ variable }}, a name made of letters, digits, _ and ., with the
+// answer given so far, the answer in the answers file, the machine hostname or, for
+// env.NAME, the environment variable. any other {{ is left as written and
+// {{{{ name }}}} is written as a literal {{ name }}, e.g. for a jinja template
+fn render_template(template:&str, scope:&Yaml, answer_conf:&Yaml)-> String{
+    let variables = Regex::new(r"\{\{\{\{(\s*[A-Za-z0-9_.]+\s*)\}\}\}\}|\{\{\s*([A-Za-z0-9_.]+)\s*\}\}").unwrap();
+    variables.replace_all(template, |caps:&regex::Captures| {
+        if let Some(escaped) = caps.get(1) {
+            return format!("{{{{{}}}}}", escaped.as_str());
+        }
+        let var = &caps[2];
+        match template_variable(var, scope, answer_conf) {
+            Some(x) => x,
+            None => panic!("{}", tr("Unknown variable \"{}\" in template \"{}\" must be a previous answer, an answer in the answers file, hostname or env.NAME", &[&var, &template]))
+        }
+    }).to_string()
+}
+
+fn template_variable(var:&str, scope:&Yaml, answer_conf:&Yaml)-> Option<String>{
+    for source in [scope, answer_conf] {
+        // dotted names reach into hash answers, e.g. database.host
+        let mut val = source;
+        for part in var.split('.') {
+            val = &val[part];
+        }
+        if val.is_badvalue() == false {
+            return Some(answer_strings(val).join(", "));
+        }
+    }
+    // built-ins come last so they never hide an answer of the same name
+    if let Some(name) = var.strip_prefix("env.") {
+        return std::env::var(name).ok();
+    }
+    if var == "hostname" {
+        return Some(hostname());
+    }
+    None
+}
+
+fn hostname()-> String{
+    let mut buf = [0u8; 256];
+    if unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) } != 0 {
+        panic!("Could not read the hostname: {}", io::Error::last_os_error());
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).to_string()
+}
+
+// the string values of an answer, a plain value for single answers
+fn answer_strings(val:&Yaml)-> Vec<String>{
+    match val {
+        Yaml::Array(x) => x.iter().filter(|v| v.as_hash().is_none() && v.as_vec().is_none()).map(yaml_to_string).collect(),
+        Yaml::Hash(_) | Yaml::BadValue => Vec::new(),
+        _ => vec![yaml_to_string(val)]
+    }
+}
+
+fn condition_met(cfg:&Yaml, scope:&Yaml)-> bool{
+    if cfg["when"].is_badvalue() {
+        return true;
+    }
+    if let Some(x) = cfg["when"].as_str(){
+        eval_condition(x, scope)
+    }else{
+        panic!("when attribute must be a string!");
+    }
+}
+
+// when expressions: terms joined with "and" / "or", each term being one of
+// name, !name, name == value, name != value or name contains value, where
+// quoted values may contain the keywords and operators, e.g. title == 'war and peace'
+fn eval_condition(expr:&str, scope:&Yaml)-> bool{
+    split_unquoted(expr, " or ").into_iter().any(|alternative| {
+        split_unquoted(alternative, " and ").into_iter().all(|term| eval_term(term.trim(), scope))
+    })
+}
+
+fn eval_term(term:&str, scope:&Yaml)-> bool{
+    let unquote = |v:&str| v.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+    let values = |n:&str| answer_strings(&scope[n.trim()]);
+
+    match split_term(term) {
+        Some((name, "!=", value)) => values(name) != vec![unquote(value)],
+        Some((name, "==", value)) => values(name) == vec![unquote(value)],
+        Some((name, _, value)) => values(name).contains(&unquote(value)),
+        None => match term.strip_prefix('!') {
+            Some(name) => is_truthy(&values(name)) == false,
+            None => is_truthy(&values(term))
+        }
+    }
+}
+
+// the name, operator and value of a comparison term, the first operator outside quotes wins
+fn split_term(term:&str)-> Option<(&str, &str, &str)>{
+    ["!=", "==", " contains "].iter()
+        .filter_map(|op| find_unquoted(term, op).map(|i| (i, *op)))
+        .min_by_key(|(i, _)| *i)
+        .map(|(i, op)| (&term[..i], op.trim(), &term[i + op.len()..]))
+}
+
+// the position of the first sep that is not inside single or double quotes
+fn find_unquoted(val:&str, sep:&str)-> Option<usize>{
+    let mut quote: Option<char> = None;
+    for (i, c) in val.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {},
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if val[i..].starts_with(sep) => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+fn split_unquoted<'a>(val:&'a str, sep:&str)-> Vec<&'a str>{
+    let mut parts = Vec::new();
+    let mut rest = val;
+    while let Some(i) = find_unquoted(rest, sep) {
+        parts.push(&rest[..i]);
+        rest = &rest[i + sep.len()..];
+    }
+    parts.push(rest);
+    parts
+}
+
+// the question names a when expression refers to
+fn condition_names(expr:&str)-> Vec<String>{
+    let mut names = Vec::new();
+    for alternative in split_unquoted(expr, " or ") {
+        for term in split_unquoted(alternative, " and ") {
+            let term = term.trim();
+            let name = match split_term(term) {
+                Some((name, _, _)) => name,
+                None => term.trim_start_matches('!')
+            };
+            names.push(name.trim().to_string());
+        }
+    }
+    names
+}
+
+fn is_truthy(values:&[String])-> bool{
+    values.iter().any(|v| v.is_empty() == false && v != "false")
+}
+
+fn group(args:&Args, cfg:&Yaml, answer_conf:&Yaml, scope:&Yaml)-> Result<Yaml, GoBack>{
+    let questions = if let Some(x) = cfg["questions"].as_vec(){
+        x
+    }else{
+        panic!("questions attribute must be an array!");
+    };
+
+    let repeat = if cfg["repeat"].is_badvalue() == false {
+        if let Some(x) = cfg["repeat"].as_bool(){
+            x
+        }else{
+            panic!("repeat attribute must be a bool!");
+        }
+    }else{
+        true
+    };
+
+    let min_items = if cfg["min_items"].is_badvalue() == false {
+        if let Some(x) = cfg["min_items"].as_i64(){
+            usize::try_from(x).unwrap()
+        }else{
+            panic!("min_items attribute must be a i64!");
+        }
+    }else{
+        0
+    };
+
+    let max_items = if cfg["max_items"].is_badvalue() == false {
+        if let Some(x) = cfg["max_items"].as_i64(){
+            usize::try_from(x).unwrap()
+        }else{
+            panic!("max_items attribute must be a i64!");
+        }
+    }else{
+        usize::MAX
+    };
+
+    let default_add_message = tr("Add another?", &[]);
+    let add_message = if cfg["add_message"].is_badvalue() == false {
+        if let Some(x) = cfg["add_message"].as_str(){
+            x
+        }else{
+            panic!("add_message attribute must be a string!");
+        }
+    }else{
+        &default_add_message
+    };
+
+    // the answers of the group in the answers file, a list of items when repeating
+    let existing = answer_conf[question_name(cfg)].clone();
+    // the items answered before going back to the group, their answers are the defaults
+    let previous = &cfg["default"];
+
+    if repeat == false {
+        if let Some(msg) = cfg["message"].as_str(){
+            println!("{}", msg);
+        }
+        let defaults = Some(previous).filter(|p| p.as_hash().is_some());
+        return group_item(args, questions, answer_conf, scope, defaults, &existing);
+    }
+
+    // every item before its formatters, used as defaults when going back into it
+    let mut items: Vec<Yaml> = Vec::new();
+    // the answers of an item the user went back to, used as its defaults
+    let mut defaults: Option<Yaml> = None;
+    let mut confirmed = false;
+    while items.len() < max_items {
+        if confirmed == false && items.len() >= min_items {
+            let msg = if items.is_empty() {
+                cfg["message"].as_str().unwrap_or(add_message)
+            }else{
+                add_message
+            };
+            let more = items.is_empty() || previous[items.len()].as_hash().is_some();
+            match prompted(Confirm::new(msg).with_default(more).prompt()) {
+                Ok(false) => break,
+                Ok(true) => {},
+                // esc on the confirmation goes back into the last item
+                Err(GoBack) => {
+                    defaults = Some(items.pop().ok_or(GoBack)?);
+                }
+            }
+            confirmed = true;
+        }else if items.is_empty() && defaults.is_none() {
+            if let Some(msg) = cfg["message"].as_str(){
+                println!("{}", msg);
+            }
+        }
+        let item_defaults = defaults.as_ref().or(Some(&previous[items.len()]).filter(|p| p.as_hash().is_some()));
+        match group_item(args, questions, answer_conf, scope, item_defaults, &existing[items.len()]) {
+            Ok(item) => {
+                items.push(item);
+                defaults = None;
+                confirmed = false;
+            },
+            // esc on the first question of an item goes back to its confirmation,
+            // or into the previous item when it did not need one
+            Err(GoBack) => {
+                if items.len() >= min_items {
+                    confirmed = false;
+                }else{
+                    defaults = Some(items.pop().ok_or(GoBack)?);
+                }
+            }
+        }
+    }
+    Ok(Yaml::Array(items))
+}
+
+// asks the group questions once, siblings are visible to when and templates.
+// esc goes back to the previous sub-question, or out of the item from the first one.
+// defaults are the answers of the item when going back into it, existing the item
+// in the answers file. returns the item before the formatters
+fn group_item(args:&Args, questions:&[Yaml], answer_conf:&Yaml, scope:&Yaml, defaults:Option<&Yaml>, existing:&Yaml)-> Result<Yaml, GoBack>{
+    let mut answers: Vec<Option<Answer>> = Vec::new();
+    let mut previous: Vec<Option<Yaml>> = questions.iter().map(|q| {
+        defaults.map(|d| d[question_name(q)].clone())
+            .filter(|v| v.is_badvalue() == false)
+            .or_else(|| existing_entries(q, existing))
+    }).collect();
+
+    while answers.len() < questions.len() {
+        let index = answers.len();
+        let q = &questions[index];
+
+        let mut item_scope = scope.as_hash().unwrap().clone();
+        for a in answers.iter().flatten() {
+            item_scope.insert(Yaml::String(a.name.clone()), a.answer.clone());
+        }
+        let item_scope = Yaml::Hash(item_scope);
+
+        if condition_met(q, &item_scope) == false {
+            answers.push(None);
+            continue;
+        }
+        let mut rendered = render_cfg(q, &item_scope, answer_conf);
+        if let Some(p) = &previous[index] {
+            rendered = with_previous_answer(&rendered, p);
+        }
+        if let Some(answer) = timeout_answer(&rendered) {
+            previous[index] = Some(answer.clone());
+            answers.push(Some(answered(q, answer)));
+            continue;
+        }
+        match ask(args, &rendered, answer_conf, &item_scope) {
+            Ok(answer) => {
+                previous[index] = answer.clone();
+                answers.push(answer.map(|answer| answered(q, answer)));
+            },
+            Err(GoBack) => {
+                let back_to = (0..index).rev().find(|&i| {
+                    answers[i].is_some() && questions[i]["type"].as_str() != Some("computed")
+                });
+                answers.truncate(back_to.ok_or(GoBack)?);
+            }
+        }
+    }
+
+    let mut raw = LinkedHashMap::new();
+    for a in answers.into_iter().flatten() {
+        raw.insert(Yaml::String(a.name), a.raw);
+    }
+    Ok(Yaml::Hash(raw))
+}
+
+// lists every answer and lets the user re-answer any of them until they confirm
+fn review(args:&Args, cfgs:&Yaml, result:&mut [Option<Answer>], answer_conf:&Yaml){
+    let questions = cfgs.as_vec().unwrap();
+    let confirm_option = tr("Looks good, write the answers", &[]);
+
+    loop {
+        let mut options = vec![confirm_option.clone()];
+        let mut indexes = Vec::new();
+        for (i, answer) in result.iter().enumerate() {
+            if let Some(a) = answer {
+                let shown = display_answer(&masked_answer(&questions[i], &format_answer(&questions[i], "display_formatters", a.answer.clone())));
+                options.push(format!("{}: {}", a.name, shown));
+                indexes.push(i);
+            }
+        }
+
+        let message = tr("Review your answers, pick one to change it", &[]);
+        let help = tr("↑↓ to move, enter to change the answer", &[]);
+        // only the confirm entry writes the answers, esc shows the list again
+        // and ctrl-c and io errors still abort
+        let choice = match Select::new(&message, options.clone())
+            .with_help_message(&help)
+            .prompt() {
+            Err(InquireError::OperationCanceled) => continue,
+            answer => answer.unwrap()
+        };
+        if choice == confirm_option {
+            break;
+        }
+
+        let index = indexes[options.iter().position(|o| o == &choice).unwrap() - 1];
+        let cfg = &questions[index];
+        let scope = answers_scope(&result[..index]);
+        let previous = result[index].as_ref().map(|a| a.raw.clone());
+        let mut rendered = render_cfg(cfg, &scope, answer_conf);
+        if let Some(p) = &previous {
+            rendered = with_previous_answer(&rendered, p);
+        }
+        // esc while changing an answer keeps the previous one
+        if let Ok(answer) = ask(args, &rendered, answer_conf, &scope) {
+            result[index] = answer.map(|answer| answered(cfg, answer));
+        }
+
+        refresh_answers(args, cfgs, result, answer_conf, index + 1);
+    }
+}
+
+// re-evaluates the questions after a changed answer: the ones whose when no longer
+// holds are dropped, newly applicable ones are asked and computed values updated
+fn refresh_answers(args:&Args, cfgs:&Yaml, result:&mut [Option<Answer>], answer_conf:&Yaml, from:usize){
+    let questions = cfgs.as_vec().unwrap();
+    for j in from..questions.len() {
+        let cfg = &questions[j];
+        let scope = answers_scope(&result[..j]);
+        if condition_met(cfg, &scope) == false {
+            result[j] = None;
+            continue;
+        }
+        let t = cfg["type"].as_str();
+        if t == Some("note") {
+            continue;
+        }
+        if result[j].is_none() || t == Some("computed") {
+            let rendered = render_cfg(cfg, &scope, answer_conf);
+            // the review only lists answered questions, so esc asks again instead
+            // of leaving a newly applicable question unanswered
+            let answer = loop {
+                if let Ok(answer) = ask(args, &rendered, answer_conf, &scope) {
+                    break answer;
+                }
+            };
+            result[j] = answer.map(|answer| answered(cfg, answer));
+        }
+    }
+}
+
+// a copy of the question using a previous answer as its default
+fn with_previous_answer(cfg:&Yaml, previous:&Yaml)-> Yaml{
+    let mut updated = cfg.as_hash().unwrap().clone();
+    let values = answer_strings(previous);
+    let mut set = |key:&str, val:Yaml| {
+        updated.insert(Yaml::String(key.to_string()), val);
+    };
+    let options: Vec<String> = option_choices(cfg).into_iter().map(|o| o.value).collect();
+
+    match cfg["type"].as_str().unwrap_or("") {
+        "text" | "path" | "date_select" => {
+            if let Some(v) = values.first() {
+                set("default", Yaml::String(v.clone()));
+            }
+        },
+        "confirm" => {
+            if let Some(v) = values.first() {
+                set("default", Yaml::Boolean(v == "true"));
+            }
+        },
+        "select" => {
+            if let Some(i) = values.first().and_then(|v| options.iter().position(|o| o == v)) {
+                set("starting_cursor", Yaml::Integer(i as i64));
+            }
+        },
+        "multi_select" => {
+            let indexes = values.iter()
+                .filter_map(|v| options.iter().position(|o| o == v))
+                .map(|i| Yaml::Integer(i as i64))
+                .collect();
+            set("default", Yaml::Array(indexes));
+        },
+        "list" | "rank" => {
+            set("default", string_array(values));
+        },
+        // a parsed answer is written back as a document so no edit is lost
+        "editor" if editor_rules(cfg).parse.is_some() => {
+            set("predefined_text", Yaml::String(document_text(previous)));
+            updated.remove(&Yaml::String("predefined_file".to_string()));
+        },
+        "editor" | "multiline" => {
+            if let Some(v) = values.first() {
+                set("predefined_text", Yaml::String(v.clone()));
+                updated.remove(&Yaml::String("predefined_file".to_string()));
+            }
+        },
+        "map" if previous.as_hash().is_some() => {
+            set("default", previous.clone());
+        },
+        "group" if previous.as_hash().is_some() || previous.as_vec().is_some() => {
+            set("default", previous.clone());
+        },
+        _ => {}
+    }
+    Yaml::Hash(updated)
+}
+
+// passwords replaced by a mask, including the ones answered inside groups
+fn masked_answer(cfg:&Yaml, val:&Yaml)-> Yaml{
+    let masked_item = |item:&Yaml| {
+        let mut masked = LinkedHashMap::new();
+        for (k, v) in item.as_hash().unwrap() {
+            let question = cfg["questions"].as_vec().unwrap().iter().find(|q| q["name"] == *k);
+            let v = match question {
+                Some(q) => masked_answer(q, v),
+                None => v.clone()
+            };
+            masked.insert(k.clone(), v);
+        }
+        Yaml::Hash(masked)
+    };
+
+    match (cfg["type"].as_str(), val) {
+        (Some("password"), _) => Yaml::String("********".to_string()),
+        (Some("group"), Yaml::Array(items)) => Yaml::Array(items.iter().map(masked_item).collect()),
+        (Some("group"), Yaml::Hash(_)) => masked_item(val),
+        _ => val.clone()
+    }
+}
+
+// a one line rendering of an answer for summaries
+fn display_answer(val:&Yaml)-> String{
+    match val {
+        Yaml::Array(x) if x.iter().all(|v| v.as_hash().is_none()) => answer_strings(val).join(", "),
+        Yaml::Array(x) => format!("{} items", x.len()),
+        Yaml::Hash(x) => x.iter()
+            .map(|(k, v)| format!("{}={}", yaml_to_string(k), display_answer(v)))
+            .collect::<Vec<String>>()
+            .join(", "),
+        _ => answer_strings(val).join(", ")
+    }
+}
+
+// if the answer_conf has a hash key that the answer list does not have then add it 
+fn write_output(args:&Args, cfg:&Yaml, answer_list:Vec<Option<Answer>>, answer_conf:Yaml){
+    let mut output_conf = answer_conf.clone();
+    let mut secrets: LinkedHashMap<String, LinkedHashMap<Yaml, Yaml>> = LinkedHashMap::new();
+
+    for answer in answer_list.into_iter().flatten() {
+        let (value, secret_values) = match question_cfg(cfg, &answer.name) {
+            Some(q) => output_parts(q, output_value(q, answer.answer)),
+            None => (Some(answer.answer), LinkedHashMap::new())
+        };
+
+        output_conf = if let Yaml::Hash(mut x) = output_conf.clone() {
+            let key = Yaml::String(answer.name.clone());
+            match value {
+                Some(v) => {
+                    x.insert(key.clone(), v);
+                },
+                None => {
+                    x.remove(&key);
+                }
+            }
+            for (secrets_file, v) in secret_values {
+                secrets.entry(secrets_file).or_default().insert(key.clone(), v);
+            }
+            Yaml::Hash(x)
+        }else{
+            panic!("Should be unreachable");
+        };
+    }
+
+    for (secrets_file, values) in secrets {
+        write_secrets_file(&secrets_file, values);
+    }
+    
+    let mut out_str = String::new();
+    let mut emitter = YamlEmitter::new(&mut out_str);
+    emitter.dump(&output_conf).unwrap(); // dump the YAML object to a String
+
+    let mut file = OpenOptions::new()
+        .read(false)
+        .write(true)
+        .create(false)
+        .truncate(true)
+        .open(args.output_answers_file.clone().unwrap())
+        .unwrap();
+    
+    
+    file.write_all(out_str.as_bytes()).unwrap();
+    file.sync_all().unwrap();
+    // dbg!(output_conf);
+    // dbg!(out_str);
+
+}
+
+// splits an answer by output policy into the value written to the answers file,
+// None when nothing is left, and the values going to each secrets file. group
+// sub-questions keep their own policy, secrets of repeating groups are written
+// as a list with one hash per item
+fn output_parts(cfg:&Yaml, val:Yaml)-> (Option<Yaml>, LinkedHashMap<String, Yaml>){
+    let mut secrets = LinkedHashMap::new();
+    match output_policy(cfg).as_str() {
+        "omit" => return (None, secrets),
+        "secrets_file" => {
+            secrets.insert(cfg["secrets_file"].as_str().unwrap().to_string(), val);
+            return (None, secrets);
+        },
+        _ => {}
+    }
+
+    let item_parts = |item:Yaml| {
+        let mut kept = LinkedHashMap::new();
+        let mut item_secrets: LinkedHashMap<String, LinkedHashMap<Yaml, Yaml>> = LinkedHashMap::new();
+        match item {
+            Yaml::Hash(x) => {
+                for (k, v) in x {
+                    let sub = match k.as_str() {
+                        Some(name) => question_cfg(&cfg["questions"], name),
+                        None => None
+                    };
+                    let (v, sub_secrets) = match sub {
+                        Some(q) => output_parts(q, v),
+                        None => (Some(v), LinkedHashMap::new())
+                    };
+                    if let Some(v) = v {
+                        kept.insert(k.clone(), v);
+                    }
+                    for (secrets_file, v) in sub_secrets {
+                        item_secrets.entry(secrets_file).or_default().insert(k.clone(), v);
+                    }
+                }
+                (Yaml::Hash(kept), item_secrets)
+            },
+            _ => (item, item_secrets)
+        }
+    };
+
+    if cfg["type"].as_str() != Some("group") {
+        return (Some(val), secrets);
+    }
+    match val {
+        Yaml::Array(items) => {
+            let parts: Vec<_> = items.into_iter().map(item_parts).collect();
+            for (_, item_secrets) in &parts {
+                for secrets_file in item_secrets.keys() {
+                    secrets.insert(secrets_file.clone(), Yaml::Array(Vec::new()));
+                }
+            }
+            // every item gets an entry so the secrets line up with the answers
+            for (secrets_file, list) in secrets.iter_mut() {
+                if let Yaml::Array(x) = list {
+                    for (_, item_secrets) in &parts {
+                        let item = item_secrets.get(secrets_file).cloned().unwrap_or_default();
+                        x.push(Yaml::Hash(item));
+                    }
+                }
+            }
+            (Some(Yaml::Array(parts.into_iter().map(|(kept, _)| kept).collect())), secrets)
+        },
+        Yaml::Hash(_) => {
+            let (kept, item_secrets) = item_parts(val);
+            for (secrets_file, x) in item_secrets {
+                secrets.insert(secrets_file, Yaml::Hash(x));
+            }
+            (Some(kept), secrets)
+        },
+        _ => (Some(val), secrets)
+    }
+}
+
+// an answer derived from earlier answers without prompting, either a value
+// template or a when style expression stored as "true" / "false"
+fn computed(_args:&Args, cfg:&Yaml, answer_conf:&Yaml, scope:&Yaml)-> Vec<String>{
+    if cfg["value"].is_badvalue() == false {
+        if cfg["expression"].is_badvalue() == false {
+            panic!("value and expression can not both be defined!");
+        }
+        if let Some(x) = cfg["value"].as_str(){
+            vec![render_template(x, scope, answer_conf)]
+        }else{
+            panic!("value attribute must be a string!");
+        }
+    }else if cfg["expression"].is_badvalue() == false {
+        if let Some(x) = cfg["expression"].as_str(){
+            vec![eval_condition(x, scope).to_string()]
+        }else{
+            panic!("expression attribute must be a string!");
+        }
+    }else{
+        panic!("computed requires either a value or an expression!");
+    }
+}
+
+// prints a section header or explanation, nothing is written to the answers file
+fn note(_args:&Args, cfg:&Yaml){
+    // there is nobody to read it when not attached to a terminal
+    if termion::is_tty(&io::stdin()) == false {
+        return;
+    }
+
+    let color = std::env::var("NO_COLOR").is_err();
+
+    if cfg["title"].is_badvalue() == false {
+        if let Some(x) = cfg["title"].as_str(){
+            if color {
+                println!("{}{}{}", style::Bold, x, style::Reset);
+            }else{
+                println!("{}", x);
+            }
+        }else{
+            panic!("title attribute must be a string!");
+        }
+    }
+
+    if cfg["message"].is_badvalue() == false {
+        if let Some(x) = cfg["message"].as_str(){
+            println!("{}", x);
+        }else{
+            panic!("message attribute must be a string!");
+        }
+    }
+
+    let wait = if cfg["wait"].is_badvalue() == false {
+        if let Some(x) = cfg["wait"].as_bool(){
+            x
+        }else{
+            panic!("wait attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    if wait {
+        if color {
+            print!("{}{}{}", style::Faint, tr("Press enter to continue", &[]), style::Reset);
+        }else{
+            print!("{}", tr("Press enter to continue", &[]));
+        }
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        io::stdin().read_line(&mut line).unwrap();
+    }
+    println!();
+}
+
+fn confirm(_args:&Args, cfg:&Yaml)-> Result<Vec<String>, GoBack>{
+    let mut inq = if let Some(msg) = cfg["message"].as_str(){
+        Confirm::new(msg)
+    }else {
+        panic!("Error: A message is required to inquired.");
+    };
+
+
+    if cfg["help"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["help"].as_str(){
+            inq.with_help_message(x)
+        }else{
+            panic!("help attribute must be a string!");
+        };
+    }
+
+    if cfg["default"].is_badvalue() == false {
+        inq = if let Some(default) = cfg["default"].as_bool(){
+            inq.with_default(default)
+        }else{
+            panic!("default attribute must be a boolean!");
+        };
+    }
+
+    if cfg["placeholder"].is_badvalue() == false {
+        inq = if let Some(placeholder) = cfg["placeholder"].as_str(){
+            inq.with_placeholder(placeholder)
+        }else{
+            panic!("placeholder attribute must be a string!");
+        };
+    }
+
+    // accepted answers other than y/yes and n/no, e.g. si/no, the first word of
+    // each list is the one echoed
+    let yes_words = confirm_words(cfg, "yes_words", &["yes", "y"]);
+    let no_words = confirm_words(cfg, "no_words", &["no", "n"]);
+    let parser = |val:&str| {
+        let val = val.trim().to_lowercase();
+        if yes_words.contains(&val) {
+            Ok(true)
+        }else if no_words.contains(&val) {
+            Ok(false)
+        }else{
+            Err(())
+        }
+    };
+    let formatter = |val:bool| if val { yes_words[0].clone() } else { no_words[0].clone() };
+    let default_formatter = |val:bool| {
+        let yes = yes_words[0].chars().next().unwrap().to_string();
+        let no = no_words[0].chars().next().unwrap().to_string();
+        if val {
+            format!("{}/{}", yes.to_uppercase(), no)
+        }else{
+            format!("{}/{}", yes, no.to_uppercase())
+        }
+    };
+    let error_message = tr("Type {} or {}", &[&yes_words[0], &no_words[0]]);
+    if cfg["yes_words"].is_badvalue() == false || cfg["no_words"].is_badvalue() == false {
+        inq = inq.with_parser(&parser)
+            .with_formatter(&formatter)
+            .with_default_value_formatter(&default_formatter)
+            .with_error_message(&error_message);
+    }
+
+    let answer = if cfg["skippable"].is_badvalue() == false {
+        if let Some(x) = cfg["skippable"].as_bool(){
+            if x {
+                if let Some(r) = inq.prompt_skippable().unwrap(){
+                    let val = if r {
+                        "true"
+                    } else {
+                        "false"
+                    };
+                    vec![val.to_string()]
+                }else{
+                    Vec::new()
+                }
+            }else{
+                let val = if prompted(inq.prompt())? {
+                    "true"
+                } else {
+                    "false"
+                };
+                vec![val.to_string()]
+            }
+        }else{
+            panic!("skippable attribute must be a bool!");
+        }
+    }else{
+        let val = if prompted(inq.prompt())? {
+            "true"
+        } else {
+            "false"
+        };
+        vec![val.to_string()]
+    };
+    Ok(answer)
+}
+
+fn confirm_words(cfg:&Yaml, attribute:&str, default:&[&str])-> Vec<String>{
+    if cfg[attribute].is_badvalue() {
+        return default.iter().map(|w| w.to_string()).collect();
+    }
+    let words: Vec<String> = if let Some(x) = cfg[attribute].as_vec(){
+        x.iter().map(|w| w.as_str().expect("confirm words must be strings!").trim().to_lowercase()).collect()
+    }else{
+        panic!("{} attribute must be an array!", attribute);
+    };
+    if words.is_empty() || words.iter().any(|w| w.is_empty()) {
+        panic!("{} attribute must hold at least one non empty word!", attribute);
+    }
+    words
+}
+
+// confirm answers are kept as "true" / "false" for when and templates and only
+// written as the question's true_value / false_value, group items included
+fn output_value(cfg:&Yaml, val:Yaml)-> Yaml{
+    match cfg["type"].as_str() {
+        Some("confirm") => {
+            let mapped = |attribute:&str, fallback:&str| match &cfg[attribute] {
+                Yaml::BadValue => fallback.to_string(),
+                Yaml::String(_) | Yaml::Integer(_) | Yaml::Boolean(_) | Yaml::Real(_) => yaml_to_string(&cfg[attribute]),
+                _ => panic!("{} attribute must be a string, a number or a boolean!", attribute)
+            };
+            let values = answer_strings(&val).into_iter().map(|v| match v.as_str() {
+                "true" => mapped("true_value", "true"),
+                "false" => mapped("false_value", "false"),
+                _ => v
+            }).collect();
+            string_array(values)
+        },
+        Some("group") => {
+            let item_value = |item:Yaml| match item {
+                Yaml::Hash(x) => Yaml::Hash(x.into_iter().map(|(k, v)| {
+                    let sub = match k.as_str() {
+                        Some(name) => question_cfg(&cfg["questions"], name),
+                        None => None
+                    };
+                    let v = match sub {
+                        Some(q) => output_value(q, v),
+                        None => v
+                    };
+                    (k, v)
+                }).collect()),
+                _ => item
+            };
+            // repeating groups answer a list of items, repeat: false a single one
+            match val {
+                Yaml::Array(items) => Yaml::Array(items.into_iter().map(item_value).collect()),
+                Yaml::Hash(_) => item_value(val),
+                _ => val
+            }
+        },
+        _ => val
+    }
+}
+
+fn text(_args:&Args, cfg:&Yaml)-> Result<Vec<String>, GoBack>{
+    let mut inq = if let Some(msg) = cfg["message"].as_str(){
+        Text::new(msg)
+    }else {
+        panic!("Error: A message is required to inquired.");
+    };
+
+    if cfg["help"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["help"].as_str(){
+            inq.with_help_message(x)
+        }else{
+            panic!("help attribute must be a string!");
+        };
+    }
+
+    if cfg["default"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["default"].as_str(){
+            inq.with_default(x)
+        }else{
+            panic!("default attribute must be a string!");
+        };
+    }
+
+    if cfg["page_size"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["page_size"].as_i64(){
+            inq.with_page_size(x.try_into().unwrap())
+        }else{
+            panic!("help attribute must be a string!");
+        };
+    }
+
+    if cfg["initial_value"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["initial_value"].as_str(){
+            inq.with_initial_value(x)
+        }else{
+            panic!("help attribute must be a string!");
+        };
+    }
+    let sub;
+    if cfg["suggestions"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["suggestions"].as_vec(){
+            
+            let mut suggestions = Vec::new();
+            for a in x {
+                let z = String::from(a.as_str().unwrap());
+                suggestions.push(z);
+            }            
+
+            sub = move |val :&str| {
+                let sugg = suggestions.clone();
+                let val_lower = val.to_lowercase();
+                sugg
+                    .iter()
+                    .filter(|s| s.to_lowercase().contains(&val_lower))
+                    .map(String::from)
+                    .collect()
+            };
+            inq.with_suggester(&sub)
+        }else{
+            panic!("help attribute must be a string!");
+        };
+    }
+
+    if cfg["placeholder"].is_badvalue() == false {
+        inq = if let Some(placeholder) = cfg["placeholder"].as_str(){
+            inq.with_placeholder(placeholder)
+        }else{
+            panic!("placeholder attribute must be a string!");
+        };
+    }
+
+    let mask = if cfg["mask"].is_badvalue() == false {
+        if let Some(x) = cfg["mask"].as_str(){
+            if cfg["suggestions"].is_badvalue() == false {
+                panic!("mask and suggestions can not both be defined!");
+            }
+            Some(x)
+        }else{
+            panic!("mask attribute must be a string!");
+        }
+    }else{
+        None
+    };
+
+    let validator = |val :&str| validate_mask(cfg, val).and_then(|_| validate_string(cfg, val));
+    inq = inq.with_validator(&validator);
+
+    // the echoed answer is the stored value passed through the display formatters
+    let formatter = |val :&str| format_string(cfg, "display_formatters", &format_string(cfg, "formatters", val));
+    inq = inq.with_formatter(&formatter);
+
+    let skippable = if cfg["skippable"].is_badvalue() == false {
+        if let Some(x) = cfg["skippable"].as_bool(){
+            x
+        }else{
+            panic!("skippable attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    let answer = match mask {
+        Some(m) => masked_text(cfg, m),
+        None => inq.prompt()
+    };
+    if skippable {
+        match answer {
+            Err(InquireError::OperationCanceled) => Ok(Vec::new()),
+            _ => Ok(vec![answer.unwrap()])
+        }
+    }else{
+        Ok(vec![prompted(answer)?])
+    }
+}
+
+// inquire can not filter keys, so a masked text is read here and only takes the
+// characters its mask allows, drawn like inquire draws a text prompt
+fn masked_text(cfg:&Yaml, mask:&str)-> InquireResult<String>{
+    let config = render_config(cfg);
+    let help = cfg["help"].as_str().unwrap_or("");
+    let default = cfg["default"].as_str();
+    let placeholder = cfg["placeholder"].as_str().unwrap_or(mask_example(mask));
+
+    let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+    let mut keys = tty.try_clone()?.keys();
+    let mut out = tty.into_raw_mode()?;
+    let title = format!("{} {}", painted(&config.prompt_prefix), painted(&Styled::new(cfg["message"].as_str().unwrap()).with_style_sheet(config.prompt)));
+    let prompt = match default {
+        Some(d) => format!("{} {}", title, painted(&Styled::new(format!("({})", d)).with_style_sheet(config.default_value))),
+        None => title.clone()
+    };
+
+    let mut val: Vec<char> = cfg["initial_value"].as_str().unwrap_or("").chars().collect();
+    let mut cursor = val.len();
+    let mut error: Option<String> = None;
+    loop {
+        let input: String = val.iter().collect();
+        let shown = if val.is_empty() {
+            painted(&Styled::new(placeholder).with_style_sheet(config.placeholder))
+        }else{
+            painted(&Styled::new(&input).with_style_sheet(config.text_input))
+        };
+        let line = match &error {
+            Some(e) => format!("{} {}", painted(&config.error_message.prefix), painted(&Styled::new(e).with_style_sheet(config.error_message.message))),
+            None if help.is_empty() => String::new(),
+            None => painted(&Styled::new(format!("[{}]", help)).with_style_sheet(config.help_message))
+        };
+        // the cursor goes back to the start of the placeholder or to the edit position
+        let back = if val.is_empty() { placeholder.chars().count() } else { val.len() - cursor };
+        write!(out, "\r\n{}{}{}\r{}{} {}", clear::CurrentLine, line, termion::cursor::Up(1), clear::CurrentLine, prompt, shown)?;
+        if back > 0 {
+            write!(out, "{}", termion::cursor::Left(u16::try_from(back).unwrap()))?;
+        }
+        out.flush()?;
+
+        let key = match keys.next() {
+            Some(k) => k?,
+            None => return Err(InquireError::NotTTY)
+        };
+        match key {
+            Key::Char('\n') | Key::Char('\r') => {
+                let answer = match default {
+                    Some(d) if val.is_empty() => d.to_string(),
+                    _ => input
+                };
+                match validate_mask(cfg, &answer).and_then(|_| validate_string(cfg, &answer)) {
+                    Ok(()) => {
+                        // echoed like the text formatter, the stored value through the display formatters
+                        let shown = format_string(cfg, "display_formatters", &format_string(cfg, "formatters", &answer));
+                        write!(out, "\r\n{}{}\r{}{} {}\r\n", clear::CurrentLine, termion::cursor::Up(1), clear::CurrentLine, title, painted(&Styled::new(shown).with_style_sheet(config.answer)))?;
+                        return Ok(answer);
+                    },
+                    Err(e) => {
+                        error = Some(e);
+                        continue;
+                    }
+                }
+            },
+            Key::Char(c) if mask_allows(mask, c) => {
+                val.insert(cursor, c);
+                cursor += 1;
+            },
+            Key::Backspace if cursor > 0 => {
+                cursor -= 1;
+                val.remove(cursor);
+            },
+            Key::Delete if cursor < val.len() => {
+                val.remove(cursor);
+            },
+            Key::Left if cursor > 0 => cursor -= 1,
+            Key::Right if cursor < val.len() => cursor += 1,
+            Key::Home => cursor = 0,
+            Key::End => cursor = val.len(),
+            Key::Esc | Key::Ctrl('c') => {
+                write!(out, "\r\n{}{}\r{}{} {}\r\n", clear::CurrentLine, termion::cursor::Up(1), clear::CurrentLine, title, painted(&config.canceled_prompt_indicator))?;
+                return if key == Key::Esc { Err(InquireError::OperationCanceled) } else { Err(InquireError::OperationInterrupted) };
+            },
+            _ => continue
+        }
+        error = None;
+    }
+}
+
+// an example value shown as placeholder for a masked text
+fn mask_example(mask:&str)-> &'static str{
+    match mask {
+        "ipv4"     => "192.168.0.1",
+        "ipv6"     => "2001:db8::1",
+        "cidr"     => "10.0.0.0/24",
+        "mac"      => "00:1a:2b:3c:4d:5e",
+        "hostname" => "host.example.com",
+        "email"    => "user@example.com",
+        "url"      => "https://example.com/path",
+        "port"     => "8080",
+        "semver"   => "1.2.3",
+        _ => panic!("unknown mask {} must be either ipv4, ipv6, cidr, mac, hostname, email, url, port, semver", mask)
+    }
+}
+
+// checks a text answer against its "mask", characters that can never be part
+// of the format are reported first, then the full format
+fn validate_mask(cfg:&Yaml, val:&str)-> Result<(), String>{
+    if cfg["mask"].is_badvalue() {
+        return Ok(());
+    }
+    let mask = if let Some(x) = cfg["mask"].as_str(){
+        x
+    }else{
+        panic!("mask attribute must be a string!");
+    };
+    let example = mask_example(mask);
+
+    // typed answers never have them, defaults and initial values still can
+    if let Some(c) = val.chars().find(|c| mask_allows(mask, *c) == false) {
+        return Err(tr("\"{}\" is not allowed in a {} value like {}", &[&c, &mask, &example]));
+    }
+
+    let valid = match mask {
+        "ipv4" => val.parse::<Ipv4Addr>().is_ok(),
+        "ipv6" => val.parse::<Ipv6Addr>().is_ok(),
+        "cidr" => match val.split_once('/') {
+            Some((ip, bits)) => match (ip.parse::<IpAddr>(), bits.parse::<u8>()) {
+                (Ok(IpAddr::V4(_)), Ok(b)) => b <= 32 && bits.starts_with('+') == false,
+                (Ok(IpAddr::V6(_)), Ok(b)) => b <= 128 && bits.starts_with('+') == false,
+                _ => false
+            },
+            None => false
+        },
+        // one separator throughout, either 00:1a:.. or 00-1a-..
+        "mac" => Regex::new(r"^[0-9A-Fa-f]{2}(:[0-9A-Fa-f]{2}){5}$|^[0-9A-Fa-f]{2}(-[0-9A-Fa-f]{2}){5}$").unwrap().is_match(val),
+        "hostname" => valid_hostname(val),
+        "email" => match val.rsplit_once('@') {
+            Some((user, domain)) => user.is_empty() == false && user.contains('@') == false && domain.contains('.') && valid_hostname(domain),
+            None => false
+        },
+        "url" => Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*://[^/?#\s]+([/?#]\S*)?$").unwrap().is_match(val),
+        "port" => matches!(val.parse::<u16>(), Ok(p) if p > 0) && val.starts_with('0') == false,
+        "semver" => Regex::new(r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$").unwrap().is_match(val),
+        _ => true
+    };
+    if valid {
+        Ok(())
+    }else{
+        Err(tr("The answer must be a valid {} like {}", &[&mask, &example]))
+    }
+}
+
+// the characters that can be part of a value of the mask, the only ones typing takes
+fn mask_allows(mask:&str, c:char)-> bool{
+    match mask {
+        "ipv4" | "port" => c.is_ascii_digit() || (mask == "ipv4" && c == '.'),
+        "ipv6"     => c.is_ascii_hexdigit() || c == ':' || c == '.',
+        "cidr"     => c.is_ascii_hexdigit() || c == ':' || c == '.' || c == '/',
+        "mac"      => c.is_ascii_hexdigit() || c == ':' || c == '-',
+        "hostname" => c.is_ascii_alphanumeric() || c == '.' || c == '-',
+        "semver"   => c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '+',
+        _          => c.is_whitespace() == false
+    }
+}
+
+fn valid_hostname(val:&str)-> bool{
+    val.len() <= 253 && val.trim_end_matches('.').split('.').all(|label| {
+        label.is_empty() == false
+            && label.len() <= 63
+            && label.starts_with('-') == false
+            && label.ends_with('-') == false
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+// applies the "formatters" (stored value) or "display_formatters" (echo and
+// review only) of a question to every string of an answer
+fn format_answer(cfg:&Yaml, attribute:&str, val:Yaml)-> Yaml{
+    if cfg[attribute].is_badvalue() {
+        return val;
+    }
+    match val {
+        Yaml::String(x) => Yaml::String(format_string(cfg, attribute, &x)),
+        Yaml::Array(x) => Yaml::Array(x.into_iter().map(|v| format_answer(cfg, attribute, v)).collect()),
+        Yaml::Hash(x) => Yaml::Hash(x.into_iter().map(|(k, v)| (k, format_answer(cfg, attribute, v))).collect()),
+        _ => val
+    }
+}
+
+fn format_string(cfg:&Yaml, attribute:&str, val:&str)-> String{
+    if cfg[attribute].is_badvalue() {
+        return val.to_string();
+    }
+    let formatters = if let Some(x) = cfg[attribute].as_vec(){
+        x
+    }else{
+        panic!("{} attribute must be an array!", attribute);
+    };
+
+    let mut out = val.to_string();
+    for f in formatters {
+        let sub_type = if let Some(x) = f["sub_type"].as_str(){
+            x
+        }else{
+            panic!("formatter sub_type attribute must be a string!");
+        };
+        let value = &f["value"];
+
+        out = match sub_type {
+            "trim" => out.trim().to_string(),
+            "lowercase" => out.to_lowercase(),
+            "uppercase" => out.to_uppercase(),
+            "collapse_whitespace" => out.split_whitespace().collect::<Vec<&str>>().join(" "),
+            "slugify" => out.to_lowercase()
+                .split(|c:char| c.is_alphanumeric() == false)
+                .filter(|w| w.is_empty() == false)
+                .collect::<Vec<&str>>()
+                .join("-"),
+            "prefix" => format!("{}{}", value.as_str().expect("prefix value must be a string!"), out),
+            "suffix" => format!("{}{}", out, value.as_str().expect("suffix value must be a string!")),
+            "regex_replace" => {
+                let pattern = value.as_str().expect("regex_replace value must be a string!");
+                let replacement = f["replacement"].as_str().expect("regex_replace replacement must be a string!");
+                Regex::new(pattern).unwrap().replace_all(&out, replacement).to_string()
+            },
+            _ => panic!("unknown formatter sub_type {} must be either trim, lowercase, uppercase, slugify, collapse_whitespace, prefix, suffix, regex_replace", sub_type)
+        };
+    }
+    out
+}
+
+// runs the question "validators" against a string answer, the first failure is returned
+fn validate_string(cfg:&Yaml, val:&str)-> Result<(), String>{
+    if cfg["validators"].is_badvalue() {
+        return Ok(());
+    }
+    let validators = if let Some(x) = cfg["validators"].as_vec(){
+        x
+    }else{
+        panic!("validators attribute must be an array!");
+    };
+
+    for v in validators {
+        let sub_type = if let Some(x) = v["sub_type"].as_str(){
+            x
+        }else{
+            panic!("validator sub_type attribute must be a string!");
+        };
+        let value = &v["value"];
+
+        let result = match sub_type {
+            "regex_match" => {
+                let pattern = value.as_str().expect("regex_match value must be a string!");
+                if Regex::new(pattern).unwrap().is_match(val) {
+                    Ok(())
+                }else{
+                    Err(tr("The answer must match {}", &[&pattern]))
+                }
+            },
+            "min_len" => {
+                let min = value.as_i64().expect("min_len value must be a i64!");
+                if val.chars().count() as i64 >= min {
+                    Ok(())
+                }else{
+                    Err(tr("The answer must be at least {} characters long", &[&min]))
+                }
+            },
+            "max_len" => {
+                let max = value.as_i64().expect("max_len value must be a i64!");
+                if val.chars().count() as i64 <= max {
+                    Ok(())
+                }else{
+                    Err(tr("The answer must be at most {} characters long", &[&max]))
+                }
+            },
+            "file_exist" => {
+                if Path::new(&expand_path(val)).is_file() {
+                    Ok(())
+                }else{
+                    Err(tr("File {} does not exist", &[&val]))
+                }
+            },
+            "dir_exists" => {
+                if Path::new(&expand_path(val)).is_dir() {
+                    Ok(())
+                }else{
+                    Err(tr("Directory {} does not exist", &[&val]))
+                }
+            },
+            "dir_of_file_exists" => {
+                let expanded = expand_path(val);
+                match Path::new(&expanded).parent() {
+                    Some(d) if d.as_os_str().is_empty() || d.is_dir() => Ok(()),
+                    _ => Err(tr("The directory of {} does not exist", &[&val]))
+                }
+            },
+            _ => panic!("unknown validator sub_type {} must be either regex_match, min_len, max_len, file_exist, dir_exists, dir_of_file_exists", sub_type)
+        };
+
+        if let Err(e) = result {
+            return match v["message"].as_str() {
+                Some(m) => Err(m.to_string()),
+                None => Err(e)
+            };
+        }
+    }
+    Ok(())
+}
+
+
+fn list(_args:&Args, cfg:&Yaml)-> Result<Vec<String>, GoBack>{
+    let msg = if let Some(msg) = cfg["message"].as_str(){
+        msg
+    }else {
+        panic!("Error: A message is required to inquired.");
+    };
+
+    let min_items = if cfg["min_items"].is_badvalue() == false {
+        if let Some(x) = cfg["min_items"].as_i64(){
+            usize::try_from(x).unwrap()
+        }else{
+            panic!("min_items attribute must be a i64!");
+        }
+    }else{
+        0
+    };
+
+    let max_items = if cfg["max_items"].is_badvalue() == false {
+        if let Some(x) = cfg["max_items"].as_i64(){
+            usize::try_from(x).unwrap()
+        }else{
+            panic!("max_items attribute must be a i64!");
+        }
+    }else{
+        usize::MAX
+    };
+
+    if min_items > max_items {
+        panic!("min_items can not be greater than max_items!");
+    }
+
+    let mut defaults = Vec::new();
+    if cfg["default"].is_badvalue() == false {
+        if let Some(x) = cfg["default"].as_vec(){
+            for a in x {
+                defaults.push(a.as_str().unwrap().to_string());
+            }
+        }else{
+            panic!("default attribute must be an array!");
+        };
+    }
+
+    let skippable = if cfg["skippable"].is_badvalue() == false {
+        if let Some(x) = cfg["skippable"].as_bool(){
+            x
+        }else{
+            panic!("skippable attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    let mut items: Vec<String> = Vec::new();
+    // the item taken back with esc, shown again for editing
+    let mut edited: Option<String> = None;
+    while items.len() < max_items {
+        let count = items.len();
+        let item_msg = if count == 0 {
+            msg.to_string()
+        }else{
+            tr("{} (item {})", &[&msg, &(count + 1)])
+        };
+
+        let mut help = String::new();
+        if cfg["help"].is_badvalue() == false {
+            if let Some(x) = cfg["help"].as_str(){
+                help.push_str(x);
+                help.push_str(" | ");
+            }else{
+                panic!("help attribute must be a string!");
+            };
+        }
+        if count == 0 && defaults.is_empty() == false {
+            help.push_str(&tr("Empty entry keeps the default: {}", &[&defaults.join(", ")]));
+        }else{
+            help.push_str(&tr("Empty entry to finish", &[]));
+        }
+
+        let mut inq = Text::new(&item_msg).with_help_message(&help);
+
+        if cfg["placeholder"].is_badvalue() == false {
+            inq = if let Some(placeholder) = cfg["placeholder"].as_str(){
+                inq.with_placeholder(placeholder)
+            }else{
+                panic!("placeholder attribute must be a string!");
+            };
+        }
+        if let Some(x) = &edited {
+            inq = inq.with_initial_value(x);
+        }
+
+        let has_defaults = defaults.is_empty() == false;
+        let validator = move |val :&str| {
+            if val.is_empty() {
+                if count >= min_items || (count == 0 && has_defaults) {
+                    Ok(())
+                }else{
+                    Err(tr("At least {} items are required", &[&min_items]))
+                }
+            }else{
+                validate_string(cfg, val)
+            }
+        };
+        inq = inq.with_validator(&validator);
+
+        let entry = if skippable {
+            if let Some(r) = inq.prompt_skippable().unwrap(){
+                r
+            }else{
+                return Ok(Vec::new());
+            }
+        }else{
+            match prompted(inq.prompt()) {
+                Ok(r) => r,
+                // esc takes back the previous item, on the first one it leaves the question
+                Err(GoBack) => {
+                    edited = Some(items.pop().ok_or(GoBack)?);
+                    continue;
+                }
+            }
+        };
+        edited = None;
+
+        if entry.is_empty() {
+            if count == 0 && has_defaults {
+                return Ok(defaults);
+            }
+            break;
+        }
+        items.push(entry);
+    }
+    Ok(items)
+}
+
+
+fn map(_args:&Args, cfg:&Yaml)-> Result<Yaml, GoBack>{
+    let msg = if let Some(msg) = cfg["message"].as_str(){
+        msg
+    }else {
+        panic!("Error: A message is required to inquired.");
+    };
+    let name = question_name(cfg);
+
+    let key_pattern = if cfg["key_pattern"].is_badvalue() == false {
+        if let Some(x) = cfg["key_pattern"].as_str(){
+            Some(Regex::new(x).unwrap())
+        }else{
+            panic!("key_pattern attribute must be a string!");
+        }
+    }else{
+        None
+    };
+
+    let skippable = if cfg["skippable"].is_badvalue() == false {
+        if let Some(x) = cfg["skippable"].as_bool(){
+            x
+        }else{
+            panic!("skippable attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    // the entries of the answers file or of a previous answer are passed in as the default
+    let initial = if cfg["default"].is_badvalue() == false {
+        if let Some(x) = cfg["default"].as_hash(){
+            x.clone()
+        }else{
+            panic!("default attribute must be a hash!");
+        }
+    }else{
+        LinkedHashMap::new()
+    };
+
+    let mut entries: Vec<(String, String)> = Vec::new();
+    // nested values can not be edited as text, they are rejected instead of flattened
+    for (k, v) in initial {
+        if matches!(k, Yaml::Hash(_) | Yaml::Array(_)) {
+            panic!("Keys of map {} must be scalar values, got {:?}!", name, k);
+        }
+        let key = yaml_to_string(&k);
+        if matches!(v, Yaml::Hash(_) | Yaml::Array(_)) {
+            panic!("Entry {} of map {} must have a scalar value, got {:?}!", key, name, v);
+        }
+        entries.push((key, yaml_to_string(&v)));
+    }
+
+    let add_option = tr("Add an entry", &[]);
+    let done_option = tr("Done", &[]);
+    let empty_help = tr("Empty value removes the entry", &[]);
+
+    loop {
+        let mut options = Vec::new();
+        for (i, (k, v)) in entries.iter().enumerate() {
+            options.push(MapOption{ label : format!("{}: {}", k, v), action : MapAction::Entry(i) });
+        }
+        options.push(MapOption{ label : add_option.clone(), action : MapAction::Add });
+        options.push(MapOption{ label : done_option.clone(), action : MapAction::Done });
+
+        let mut inq = Select::new(msg, options)
+            .with_starting_cursor(entries.len());
+
+        if cfg["help"].is_badvalue() == false {
+            inq = if let Some(x) = cfg["help"].as_str(){
+                inq.with_help_message(x)
+            }else{
+                panic!("help attribute must be a string!");
+            };
+        }
+
+        if cfg["page_size"].is_badvalue() == false {
+            inq = if let Some(x) = cfg["page_size"].as_i64(){
+                inq.with_page_size(x.try_into().unwrap())
+            }else{
+                panic!("page_size attribute must be a i64!");
+            };
+        }
+
+        let choice = if skippable {
+            if let Some(r) = inq.prompt_skippable().unwrap(){
+                r
+            }else{
+                return Ok(Yaml::Hash(LinkedHashMap::new()));
+            }
+        }else{
+            prompted(inq.prompt())?
+        };
+
+        match choice.action {
+            MapAction::Done => break,
+            MapAction::Add => {
+                let existing: Vec<String> = entries.iter().map(|(k, _)| k.clone()).collect();
+                let key_validator = |val :&str| {
+                    if val.is_empty() {
+                        return Err(tr("A key is required", &[]));
+                    }
+                    if existing.iter().any(|k| k == val) {
+                        return Err(tr("Key {} already exists", &[&val]));
+                    }
+                    if let Some(re) = &key_pattern {
+                        if re.is_match(val) == false {
+                            return Err(tr("The key must match {}", &[&re.as_str()]));
+                        }
+                    }
+                    Ok(())
+                };
+                let key_msg = tr("Key:", &[]);
+                // esc on the key or the value goes back to the entries
+                let key = match prompted(Text::new(&key_msg).with_validator(&key_validator).prompt()) {
+                    Ok(x) => x,
+                    Err(GoBack) => continue
+                };
+
+                let value_msg = tr("Value for {}:", &[&key]);
+                let value_validator = |val :&str| validate_string(cfg, val);
+                let value = match prompted(Text::new(&value_msg).with_validator(&value_validator).prompt()) {
+                    Ok(x) => x,
+                    Err(GoBack) => continue
+                };
+                entries.push((key, value));
+            },
+            MapAction::Entry(index) => {
+                let (key, current) = entries[index].clone();
+
+                let value_msg = tr("Value for {}:", &[&key]);
+                let value_validator = |val :&str| {
+                    if val.is_empty() {
+                        Ok(())
+                    }else{
+                        validate_string(cfg, val)
+                    }
+                };
+                let value = match prompted(Text::new(&value_msg)
+                    .with_initial_value(&current)
+                    .with_help_message(&empty_help)
+                    .with_validator(&value_validator)
+                    .prompt()) {
+                    Ok(x) => x,
+                    Err(GoBack) => continue
+                };
+                if value.is_empty() {
+                    entries.remove(index);
+                }else{
+                    entries[index] = (key, value);
+                }
+            }
+        }
+    }
+
+    let mut result = LinkedHashMap::new();
+    for (k, v) in entries {
+        result.insert(Yaml::String(k), Yaml::String(v));
+    }
+    Ok(Yaml::Hash(result))
+}
+
+// an entry of the map menu, the existing entries carry their index so two entries
+// showing the same text can not be confused
+#[derive(Clone)]
+struct MapOption {
+    label  : String,
+    action : MapAction
+}
+
+#[derive(Clone, Copy)]
+enum MapAction {
+    Entry(usize),
+    Add,
+    Done
+}
+
+impl fmt::Display for MapOption {
+    fn fmt(&self, f:&mut fmt::Formatter)-> fmt::Result{
+        write!(f, "{}", self.label)
+    }
+}
+
+// scalar yaml values as plain strings, used when reading previous answers back
+fn yaml_to_string(val:&Yaml)-> String{
+    match val {
+        Yaml::String(x) => x.clone(),
+        Yaml::Integer(x) => x.to_string(),
+        Yaml::Real(x) => x.clone(),
+        Yaml::Boolean(x) => x.to_string(),
+        Yaml::Null => String::new(),
+        _ => panic!("Expected a scalar value but got {:?}", val)
+    }
+}
+
+
+fn path(_args:&Args, cfg:&Yaml)-> Result<Vec<String>, GoBack>{
+    let mut inq = if let Some(msg) = cfg["message"].as_str(){
+        Text::new(msg)
+    }else {
+        panic!("Error: A message is required to inquired.");
+    };
+
+    if cfg["help"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["help"].as_str(){
+            inq.with_help_message(x)
+        }else{
+            panic!("help attribute must be a string!");
+        };
+    }
+
+    if cfg["default"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["default"].as_str(){
+            inq.with_default(x)
+        }else{
+            panic!("default attribute must be a string!");
+        };
+    }
+
+    if cfg["page_size"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["page_size"].as_i64(){
+            inq.with_page_size(x.try_into().unwrap())
+        }else{
+            panic!("page_size attribute must be a i64!");
+        };
+    }
+
+    if cfg["initial_value"].is_badvalue() == false {
+        inq = if let Some(x) = cfg["initial_value"].as_str(){
+            inq.with_initial_value(x)
+        }else{
+            panic!("initial_value attribute must be a string!");
+        };
+    }
+
+    if cfg["placeholder"].is_badvalue() == false {
+        inq = if let Some(placeholder) = cfg["placeholder"].as_str(){
+            inq.with_placeholder(placeholder)
+        }else{
+            panic!("placeholder attribute must be a string!");
+        };
+    }
+
+    let rules = path_rules(cfg);
+
+    let sub = |val :&str| path_suggestions(val, &rules.path_type, &rules.extensions);
+    inq = inq.with_suggester(&sub);
+
+    let validator = |val :&str| validate_path(val, &rules);
+    inq = inq.with_validator(&validator);
+
+    let answer = if cfg["skippable"].is_badvalue() == false {
+        if let Some(x) = cfg["skippable"].as_bool(){
+            if x {
+                inq.prompt_skippable().unwrap()
+            }else{
+                Some(prompted(inq.prompt())?)
+            }
+        }else{
+            panic!("skippable attribute must be a bool!");
+        }
+    }else{
+        Some(prompted(inq.prompt())?)
     };
 
-    if cfg["help"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["help"].as_str(){
-            inq.with_help_message(x)
+    if let Some(r) = answer {
+        Ok(vec![path_answer(&rules, &r)])
+    }else{
+        Ok(Vec::new())
+    }
+}
+
+#[derive(Debug)]
+struct PathRules {
+    path_type      : String,
+    extensions     : Vec<String>,
+    must_exist     : bool,
+    must_not_exist : bool,
+    canonicalize   : bool
+}
+
+fn path_rules(cfg:&Yaml)-> PathRules{
+    let path_type = if cfg["path_type"].is_badvalue() == false {
+        if let Some(x) = cfg["path_type"].as_str(){
+            match x {
+                "file" | "dir" | "any" => x.to_string(),
+                _ => panic!("unknown path_type {} must be either file, dir, any", x)
+            }
         }else{
-            panic!("help attribute must be a string!");
+            panic!("path_type attribute must be a string!");
+        }
+    }else{
+        "any".to_string()
+    };
+
+    let mut extensions = Vec::new();
+    if cfg["extensions"].is_badvalue() == false {
+        if let Some(x) = cfg["extensions"].as_vec(){
+            for a in x {
+                extensions.push(a.as_str().unwrap().trim_start_matches('.').to_string());
+            }
+        }else{
+            panic!("extensions attribute must be an array!");
+        };
+    }
+
+    let must_exist = if cfg["must_exist"].is_badvalue() == false {
+        if let Some(x) = cfg["must_exist"].as_bool(){
+            x
+        }else{
+            panic!("must_exist attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    let must_not_exist = if cfg["must_not_exist"].is_badvalue() == false {
+        if let Some(x) = cfg["must_not_exist"].as_bool(){
+            x
+        }else{
+            panic!("must_not_exist attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    if must_exist && must_not_exist {
+        panic!("must_exist and must_not_exist can not both be true!");
+    }
+
+    let canonicalize = if cfg["canonicalize"].is_badvalue() == false {
+        if let Some(x) = cfg["canonicalize"].as_bool(){
+            x
+        }else{
+            panic!("canonicalize attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    PathRules{ path_type, extensions, must_exist, must_not_exist, canonicalize }
+}
+
+// the stored path: expanded, and made absolute when canonicalize is set
+fn path_answer(rules:&PathRules, val:&str)-> String{
+    let expanded = expand_path(val);
+    if rules.canonicalize {
+        absolute_path(&expanded)
+    }else{
+        expanded
+    }
+}
+
+// expands a leading ~ to $HOME and any $VAR or ${VAR} to its environment value,
+// unknown variables are left untouched
+fn expand_path(val:&str) -> String {
+    let mut input = val.to_string();
+    if input == "~" || input.starts_with("~/") {
+        if let Ok(home) = std::env::var("HOME") {
+            input = format!("{}{}", home, &input[1..]);
+        }
+    }
+
+    let mut out = String::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+        let mut var = String::new();
+        let braced = chars.peek() == Some(&'{');
+        let mut closed = false;
+        if braced {
+            chars.next();
+            for n in chars.by_ref() {
+                if n == '}' {
+                    closed = true;
+                    break;
+                }
+                var.push(n);
+            }
+        }else{
+            while let Some(&n) = chars.peek() {
+                if n.is_ascii_alphanumeric() || n == '_' {
+                    var.push(n);
+                    chars.next();
+                }else{
+                    break;
+                }
+            }
+        }
+        match std::env::var(&var) {
+            Ok(v) if var.is_empty() == false && braced == closed => out.push_str(&v),
+            _ => {
+                // left as typed, an unclosed ${ does not gain a }
+                out.push('$');
+                if braced {
+                    out.push('{');
+                }
+                out.push_str(&var);
+                if closed {
+                    out.push('}');
+                }
+            }
+        }
+    }
+    out
+}
+
+// canonical path when it exists, otherwise the path joined onto the current directory
+fn absolute_path(val:&str) -> String {
+    let p = Path::new(val);
+    if let Ok(c) = std::fs::canonicalize(p) {
+        c.to_string_lossy().to_string()
+    }else if p.is_absolute() {
+        val.to_string()
+    }else{
+        std::env::current_dir().unwrap().join(p).to_string_lossy().to_string()
+    }
+}
+
+fn has_extension(p:&Path, extensions:&[String]) -> bool {
+    if extensions.is_empty() {
+        return true;
+    }
+    match p.extension() {
+        Some(e) => extensions.iter().any(|x| OsStr::new(x) == e),
+        None => false
+    }
+}
+
+fn path_suggestions(val:&str, path_type:&str, extensions:&[String]) -> Vec<String> {
+    // keep what the user typed for the directory part so ~ and $VAR stay as written
+    let (typed_dir, prefix) = match val.rfind('/') {
+        Some(i) => (&val[..i + 1], &val[i + 1..]),
+        None => ("", val),
+    };
+    let read_dir = if typed_dir.is_empty() {
+        ".".to_string()
+    }else{
+        expand_path(typed_dir)
+    };
+
+    let mut suggestions = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(read_dir) {
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with(prefix) == false {
+                continue;
+            }
+            if file_name.starts_with('.') && prefix.starts_with('.') == false {
+                continue;
+            }
+            let entry_path = entry.path();
+            if entry_path.is_dir() {
+                suggestions.push(format!("{}{}/", typed_dir, file_name));
+            }else if path_type != "dir" && has_extension(&entry_path, extensions) {
+                suggestions.push(format!("{}{}", typed_dir, file_name));
+            }
+        }
+    }
+    suggestions.sort();
+    suggestions
+}
+
+fn validate_path(val:&str, rules:&PathRules) -> Result<(), String> {
+    let path_type = rules.path_type.as_str();
+    let extensions = &rules.extensions;
+    if val.is_empty() {
+        return Err(tr("A path is required.", &[]));
+    }
+    let expanded = expand_path(val);
+    let p = Path::new(&expanded);
+    if rules.must_exist && p.exists() == false {
+        return Err(tr("{} does not exist.", &[&expanded]));
+    }
+    if rules.must_not_exist && p.exists() {
+        return Err(tr("{} already exists.", &[&expanded]));
+    }
+    if path_type == "file" && p.is_dir() {
+        return Err(tr("{} is a directory, a file is required.", &[&expanded]));
+    }
+    if path_type == "dir" && p.exists() && p.is_dir() == false {
+        return Err(tr("{} is not a directory.", &[&expanded]));
+    }
+    if path_type != "dir" && p.is_dir() == false && has_extension(p, extensions) == false {
+        return Err(tr("{} must have one of the extensions: {}", &[&expanded, &extensions.join(", ")]));
+    }
+    Ok(())
+}
+
+
+fn password(_args:&Args, cfg:&Yaml)-> Result<Vec<String>, GoBack>{
+    let msg = if let Some(msg) = cfg["message"].as_str(){
+        msg
+    }else {
+        panic!("Error: A message is required to inquired.");
+    };
+
+    let confirm = if cfg["confirm"].is_badvalue() == false {
+        if let Some(x) = cfg["confirm"].as_bool(){
+            x
+        }else{
+            panic!("confirm attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    let default_confirm_message = tr("Please confirm the password:", &[]);
+    let confirm_message = if cfg["confirm_message"].is_badvalue() == false {
+        if let Some(x) = cfg["confirm_message"].as_str(){
+            x
+        }else{
+            panic!("confirm_message attribute must be a string!");
+        }
+    }else{
+        &default_confirm_message
+    };
+
+    // validate the output policy before prompting so a bad config fails early
+    let output = output_policy(cfg);
+    let algorithm = hash_algorithm(cfg);
+    let policy = password_policy(cfg);
+
+    loop {
+        let first = if let Some(r) = password_prompt(cfg, msg, policy.as_ref())?{
+            r
+        }else{
+            return Ok(Vec::new());
+        };
+
+        if confirm {
+            // esc on the confirmation asks for the password again
+            let second = match password_prompt(cfg, confirm_message, None) {
+                Ok(Some(r)) => r,
+                Ok(None) => return Ok(Vec::new()),
+                Err(GoBack) => continue
+            };
+            if first != second {
+                println!("{}", tr("The passwords do not match, please try again.", &[]));
+                continue;
+            }
+        }
+
+        // formatted here so a hash is never passed through the formatters
+        let first = format_string(cfg, "formatters", &first);
+        return if output == "hash" {
+            Ok(vec![hash_password(&algorithm, &first)])
+        }else{
+            Ok(vec![first])
         };
     }
+}
+
+fn password_prompt(cfg:&Yaml, msg:&str, policy:Option<&PasswordPolicy>)-> Result<Option<String>, GoBack>{
+    let help = if cfg["help"].is_badvalue() == false {
+        if let Some(x) = cfg["help"].as_str(){
+            x
+        }else{
+            panic!("help attribute must be a string!");
+        }
+    }else{
+        ""
+    };
 
-    if cfg["display_mode"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["display_mode"].as_str(){
-            let mode = match x {
+    let mode = if cfg["display_mode"].is_badvalue() == false {
+        if let Some(x) = cfg["display_mode"].as_str(){
+            match x {
                 "hidden" => PasswordDisplayMode::Hidden,
                 "masked" => PasswordDisplayMode::Masked,
                 "full"   => PasswordDisplayMode::Full,
                 _ => panic!("unknown display_mode {} must be wither hidden, masked, full", x)
-            };
-            inq.with_display_mode(mode)
+            }
         }else{
             panic!("display_mode attribute must be a string!");
-        };
-    }
+        }
+    }else{
+        PasswordDisplayMode::Hidden
+    };
 
-    if cfg["enable_display_toggle"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["enable_display_toggle"].as_bool(){
-            if x {
-                inq.with_display_toggle_enabled()
-            }else{
-                inq
+    let mut config = render_config(cfg);
+    if cfg["mask_character"].is_badvalue() == false {
+        if let Some(x) = cfg["mask_character"].as_str(){
+            let mut chars = x.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => config.password_mask = c,
+                _ => panic!("mask_character attribute must be a single character!")
             }
         }else{
-            panic!("help attribute must be a string!");
+            panic!("mask_character attribute must be a string!");
         };
     }
 
-    if cfg["skippable"].is_badvalue() == false {
+    let toggle = if cfg["enable_display_toggle"].is_badvalue() == false {
+        if let Some(x) = cfg["enable_display_toggle"].as_bool(){
+            x
+        }else{
+            panic!("help attribute must be a string!");
+        }
+    }else{
+        false
+    };
+
+    let skippable = if cfg["skippable"].is_badvalue() == false {
         if let Some(x) = cfg["skippable"].as_bool(){
-            if x {
-                if let Some(r) = inq.prompt_skippable().unwrap(){
-                    vec![r]
-                }else{
-                    Vec::new()
+            x
+        }else{
+            panic!("skippable attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    let answer = match policy {
+        Some(p) => live_password(msg, help, p, mode, config, toggle),
+        None => {
+            let mut inq = Password::new(msg)
+                .with_display_mode(mode)
+                .with_render_config(config);
+            if help.is_empty() == false {
+                inq = inq.with_help_message(help);
+            }
+            if toggle {
+                inq = inq.with_display_toggle_enabled();
+            }
+            inq.prompt()
+        }
+    };
+    if skippable {
+        match answer {
+            Err(InquireError::OperationCanceled) => Ok(None),
+            _ => Ok(Some(answer.unwrap()))
+        }
+    }else{
+        Ok(Some(prompted(answer)?))
+    }
+}
+
+// inquire only shows a static help message, so a password with a policy is read
+// here to update its strength in the help line on every key, drawn like inquire
+// draws a password with the question render_config
+fn live_password(msg:&str, help:&str, policy:&PasswordPolicy, mode:PasswordDisplayMode, config:RenderConfig, toggle:bool)-> InquireResult<String>{
+    let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+    let mut keys = tty.try_clone()?.keys();
+    let mut out = tty.into_raw_mode()?;
+    let prompt = format!("{} {}", painted(&config.prompt_prefix), painted(&Styled::new(msg).with_style_sheet(config.prompt)));
+    let mask = config.password_mask.to_string();
+
+    let mut val = String::new();
+    let mut shown = mode;
+    let mut rejected = false;
+    loop {
+        let typed = match shown {
+            PasswordDisplayMode::Hidden => String::new(),
+            PasswordDisplayMode::Masked => mask.repeat(val.chars().count()),
+            PasswordDisplayMode::Full => val.clone()
+        };
+        let failures = policy_failures(policy, &val);
+        let strength = if val.is_empty() {
+            tr("Requires {}", &[&policy_rules(policy).join(", ")])
+        }else if failures.is_empty() {
+            tr("Strength: {}", &[&strength_label(password_entropy(&val))])
+        }else{
+            tr("Strength: {}. Missing: {}", &[&strength_label(password_entropy(&val)), &failures.join(", ")])
+        };
+        let line = if help.is_empty() { strength } else { format!("{} | {}", help, strength) };
+        let line_style = if rejected { config.error_message.message } else { config.help_message };
+        write!(out, "\r\n{}{}{}\r{}{} {}", clear::CurrentLine, painted(&Styled::new(format!("[{}]", line)).with_style_sheet(line_style)),
+            termion::cursor::Up(1), clear::CurrentLine, prompt, painted(&Styled::new(typed).with_style_sheet(config.text_input)))?;
+        out.flush()?;
+
+        let key = match keys.next() {
+            Some(k) => k?,
+            None => return Err(InquireError::NotTTY)
+        };
+        match key {
+            Key::Char('\n') | Key::Char('\r') => {
+                if failures.is_empty() {
+                    // the answer is echoed like the inquire password formatter, without its length
+                    let answer = painted(&Styled::new(mask.repeat(8)).with_style_sheet(config.answer));
+                    write!(out, "\r\n{}{}\r{}{} {}\r\n", clear::CurrentLine, termion::cursor::Up(1), clear::CurrentLine, prompt, answer)?;
+                    return Ok(val);
                 }
+                rejected = true;
+                continue;
+            },
+            Key::Char(c) => val.push(c),
+            Key::Backspace => {
+                val.pop();
+            },
+            Key::Ctrl('r') if toggle => {
+                shown = if shown == PasswordDisplayMode::Full { mode } else { PasswordDisplayMode::Full };
+            },
+            Key::Esc | Key::Ctrl('c') => {
+                write!(out, "\r\n{}{}\r{}{} {}\r\n", clear::CurrentLine, termion::cursor::Up(1), clear::CurrentLine, prompt, painted(&config.canceled_prompt_indicator))?;
+                return if key == Key::Esc { Err(InquireError::OperationCanceled) } else { Err(InquireError::OperationInterrupted) };
+            },
+            _ => {}
+        }
+        rejected = false;
+    }
+}
+
+#[derive(Debug)]
+struct PasswordPolicy {
+    min_length  : usize,
+    lowercase   : bool,
+    uppercase   : bool,
+    digit       : bool,
+    symbol      : bool,
+    min_entropy : f64,
+    deny_list   : Vec<String>
+}
+
+fn password_policy(cfg:&Yaml)-> Option<PasswordPolicy>{
+    if cfg["policy"].is_badvalue() {
+        return None;
+    }
+    let policy = &cfg["policy"];
+    if policy.as_hash().is_none() {
+        panic!("policy attribute must be a hash!");
+    }
+
+    let flag = |key:&str| {
+        if policy[key].is_badvalue() == false {
+            if let Some(x) = policy[key].as_bool(){
+                x
             }else{
-                vec![inq.prompt().unwrap()]
+                panic!("policy {} attribute must be a bool!", key);
+            }
+        }else{
+            false
+        }
+    };
+
+    let min_length = if policy["min_length"].is_badvalue() == false {
+        if let Some(x) = policy["min_length"].as_i64(){
+            usize::try_from(x).unwrap()
+        }else{
+            panic!("policy min_length attribute must be a i64!");
+        }
+    }else{
+        0
+    };
+
+    let min_entropy = if policy["min_entropy"].is_badvalue() == false {
+        if let Some(x) = policy["min_entropy"].as_f64(){
+            x
+        }else if let Some(x) = policy["min_entropy"].as_i64(){
+            x as f64
+        }else{
+            panic!("policy min_entropy attribute must be a number!");
+        }
+    }else{
+        0.0
+    };
+
+    let mut deny_list = Vec::new();
+    if policy["deny_list_file"].is_badvalue() == false {
+        if let Some(x) = policy["deny_list_file"].as_str(){
+            let file_content = std::fs::read_to_string(expand_path(x)).unwrap();
+            for line in file_content.lines() {
+                let line = line.trim();
+                if line.is_empty() == false {
+                    deny_list.push(line.to_lowercase());
+                }
+            }
+        }else{
+            panic!("policy deny_list_file attribute must be a string!");
+        }
+    }
+
+    Some(PasswordPolicy {
+        min_length,
+        lowercase : flag("lowercase"),
+        uppercase : flag("uppercase"),
+        digit     : flag("digit"),
+        symbol    : flag("symbol"),
+        min_entropy,
+        deny_list
+    })
+}
+
+fn policy_rules(policy:&PasswordPolicy)-> Vec<String>{
+    let mut rules = Vec::new();
+    if policy.min_length > 0 {
+        rules.push(tr("at least {} characters", &[&policy.min_length]));
+    }
+    if policy.lowercase {
+        rules.push(tr("a lowercase letter", &[]));
+    }
+    if policy.uppercase {
+        rules.push(tr("an uppercase letter", &[]));
+    }
+    if policy.digit {
+        rules.push(tr("a digit", &[]));
+    }
+    if policy.symbol {
+        rules.push(tr("a symbol", &[]));
+    }
+    if policy.min_entropy > 0.0 {
+        rules.push(tr("{} bits of entropy", &[&policy.min_entropy]));
+    }
+    if policy.deny_list.is_empty() == false {
+        rules.push(tr("not a common password", &[]));
+    }
+    rules
+}
+
+fn policy_failures(policy:&PasswordPolicy, val:&str)-> Vec<String>{
+    let mut failures = Vec::new();
+    if val.chars().count() < policy.min_length {
+        failures.push(tr("at least {} characters", &[&policy.min_length]));
+    }
+    if policy.lowercase && val.chars().any(|c| c.is_lowercase()) == false {
+        failures.push(tr("a lowercase letter", &[]));
+    }
+    if policy.uppercase && val.chars().any(|c| c.is_uppercase()) == false {
+        failures.push(tr("an uppercase letter", &[]));
+    }
+    if policy.digit && val.chars().any(|c| c.is_ascii_digit()) == false {
+        failures.push(tr("a digit", &[]));
+    }
+    if policy.symbol && val.chars().any(|c| c.is_alphanumeric() == false) == false {
+        failures.push(tr("a symbol", &[]));
+    }
+    let entropy = password_entropy(val);
+    if entropy < policy.min_entropy {
+        failures.push(tr("{} bits of entropy (has {})", &[&policy.min_entropy, &format!("{:.0}", entropy)]));
+    }
+    if policy.deny_list.contains(&val.to_lowercase()) {
+        failures.push(tr("not a common password", &[]));
+    }
+    failures
+}
+
+// length times log2 of the size of the character classes in use
+fn password_entropy(val:&str)-> f64{
+    let mut pool = 0;
+    if val.chars().any(|c| c.is_ascii_lowercase()) {
+        pool += 26;
+    }
+    if val.chars().any(|c| c.is_ascii_uppercase()) {
+        pool += 26;
+    }
+    if val.chars().any(|c| c.is_ascii_digit()) {
+        pool += 10;
+    }
+    if val.chars().any(|c| c.is_ascii_punctuation() || c == ' ') {
+        pool += 33;
+    }
+    if val.chars().any(|c| c.is_ascii() == false) {
+        pool += 100;
+    }
+    if pool == 0 {
+        return 0.0;
+    }
+    val.chars().count() as f64 * (pool as f64).log2()
+}
+
+fn strength_label(entropy:f64)-> String{
+    let label = if entropy < 28.0 {
+        "Very weak"
+    }else if entropy < 36.0 {
+        "Weak"
+    }else if entropy < 60.0 {
+        "Reasonable"
+    }else if entropy < 128.0 {
+        "Strong"
+    }else{
+        "Very strong"
+    };
+    tr(label, &[])
+}
+
+// how an answer is written out: plaintext | omit | hash | secrets_file,
+// only passwords are hashed
+fn output_policy(cfg:&Yaml)-> String{
+    if cfg["output"].is_badvalue() == false {
+        if let Some(x) = cfg["output"].as_str(){
+            match x {
+                "plaintext" | "omit" => x.to_string(),
+                "hash" => {
+                    if cfg["type"].as_str() != Some("password") {
+                        panic!("output hash is only supported by password questions, use secrets_file or omit for {}!", question_name(cfg));
+                    }
+                    x.to_string()
+                },
+                "secrets_file" => {
+                    if cfg["secrets_file"].as_str().is_none() {
+                        panic!("secrets_file attribute must be a string when output is secrets_file!");
+                    }
+                    x.to_string()
+                },
+                _ => panic!("unknown output {} must be either plaintext, omit, hash, secrets_file", x)
+            }
+        }else{
+            panic!("output attribute must be a string!");
+        }
+    }else{
+        "plaintext".to_string()
+    }
+}
+
+// checked before prompting so a bad output policy does not lose the answers
+fn check_output_policies(cfgs:&Yaml){
+    for cfg in cfgs.as_vec().unwrap() {
+        output_policy(cfg);
+        if cfg["type"].as_str() == Some("group") && cfg["questions"].as_vec().is_some() {
+            check_output_policies(&cfg["questions"]);
+        }
+    }
+}
+
+// argon2 | bcrypt | sha512_crypt
+fn hash_algorithm(cfg:&Yaml)-> String{
+    if cfg["hash_algorithm"].is_badvalue() == false {
+        if let Some(x) = cfg["hash_algorithm"].as_str(){
+            match x {
+                "argon2" | "bcrypt" | "sha512_crypt" => x.to_string(),
+                _ => panic!("unknown hash_algorithm {} must be either argon2, bcrypt, sha512_crypt", x)
             }
         }else{
-            panic!("skippable attribute must be a bool!");
+            panic!("hash_algorithm attribute must be a string!");
+        }
+    }else{
+        "argon2".to_string()
+    }
+}
+
+fn hash_password(algorithm:&str, plain:&str)-> String{
+    match algorithm {
+        "argon2" => {
+            let salt = SaltString::generate(&mut OsRng);
+            Argon2::default().hash_password(plain.as_bytes(), &salt).unwrap().to_string()
+        },
+        "bcrypt"       => pwhash::bcrypt::hash(plain).unwrap(),
+        "sha512_crypt" => pwhash::sha512_crypt::hash(plain).unwrap(),
+        _ => unreachable!()
+    }
+}
+
+// merges the values into the secrets file and keeps it readable by the owner only
+fn write_secrets_file(secrets_file:&str, values:LinkedHashMap<Yaml, Yaml>){
+    let path = Path::new(secrets_file);
+    let mut secrets_conf = if path.exists() {
+        let file_content = std::fs::read_to_string(path).unwrap();
+        if file_content.is_empty() {
+            LinkedHashMap::new()
+        }else if let Yaml::Hash(x) = YamlLoader::load_from_str(&file_content).unwrap()[0].clone() {
+            x
+        }else{
+            panic!("Secrets file {} must contain a hash!", secrets_file);
         }
-
     }else{
-        vec![inq.prompt().unwrap()]
+        LinkedHashMap::new()
+    };
+
+    for (k, v) in values {
+        secrets_conf.insert(k, v);
     }
 
+    let mut out_str = String::new();
+    let mut emitter = YamlEmitter::new(&mut out_str);
+    emitter.dump(&Yaml::Hash(secrets_conf)).unwrap();
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .unwrap();
+    file.set_permissions(std::fs::Permissions::from_mode(0o600)).unwrap();
+
+    file.write_all(out_str.as_bytes()).unwrap();
+    file.sync_all().unwrap();
 }
 
+// the question config tied to an answer name
+fn question_cfg<'a>(cfgs:&'a Yaml, name:&str)-> Option<&'a Yaml>{
+    cfgs.as_vec().unwrap().iter().find(|c| c["name"].as_str() == Some(name))
+}
 
-fn editor(args:&Args, cfg:&Yaml)-> Vec<String>{
+fn editor(_args:&Args, cfg:&Yaml)-> Result<Yaml, GoBack>{
     let mut inq = if let Some(msg) = cfg["message"].as_str(){
         Editor::new(msg)
     }else {
@@ -476,37 +3346,262 @@ fn editor(args:&Args, cfg:&Yaml)-> Vec<String>{
         };
     }
 
-    if cfg["predefined_text"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["predefined_text"].as_str(){
-            inq.with_predefined_text(x)
-        }else{
-            panic!("help attribute must be a string!");
-        };
+    let predefined = editor_predefined_text(cfg);
+    if let Some(x) = &predefined {
+        inq = inq.with_predefined_text(x);
     }
 
-    if cfg["skippable"].is_badvalue() == false {
+    let rules = editor_rules(cfg);
+    let validator = |val :&str| editor_answer(cfg, &rules, val).map(|_| ());
+    inq = inq.with_validator(&validator);
+
+    let answer = if cfg["skippable"].is_badvalue() == false {
         if let Some(x) = cfg["skippable"].as_bool(){
             if x {
-                if let Some(r) = inq.prompt_skippable().unwrap(){
-                    vec![r]
-                }else{
-                    Vec::new()
-                }
+                inq.prompt_skippable().unwrap()
             }else{
-                vec![inq.prompt().unwrap()]
+                Some(prompted(inq.prompt())?)
+            }
+        }else{
+            panic!("skippable attribute must be a bool!");
+        }
+    }else{
+        Some(prompted(inq.prompt())?)
+    };
+
+    let answer = match answer {
+        Some(r) => editor_answer(cfg, &rules, &r).unwrap(),
+        None => string_array(Vec::new())
+    };
+    Ok(answer)
+}
+
+// the text the editor opens with, from predefined_text or predefined_file
+fn editor_predefined_text(cfg:&Yaml)-> Option<String>{
+    if cfg["predefined_file"].is_badvalue() == false {
+        if cfg["predefined_text"].is_badvalue() == false {
+            panic!("predefined_text and predefined_file can not both be defined!");
+        }
+        if let Some(x) = cfg["predefined_file"].as_str(){
+            Some(std::fs::read_to_string(expand_path(x)).unwrap())
+        }else{
+            panic!("predefined_file attribute must be a string!");
+        }
+    }else if cfg["predefined_text"].is_badvalue() == false {
+        if let Some(x) = cfg["predefined_text"].as_str(){
+            Some(x.to_string())
+        }else{
+            panic!("predefined_text attribute must be a string!");
+        }
+    }else{
+        None
+    }
+}
+
+#[derive(Debug)]
+struct EditorRules {
+    strip_comments : bool,
+    comment_prefix : String,
+    parse          : Option<String>
+}
+
+fn editor_rules(cfg:&Yaml)-> EditorRules{
+    let strip_comments = if cfg["strip_comments"].is_badvalue() == false {
+        if let Some(x) = cfg["strip_comments"].as_bool(){
+            x
+        }else{
+            panic!("strip_comments attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    let comment_prefix = if cfg["comment_prefix"].is_badvalue() == false {
+        if let Some(x) = cfg["comment_prefix"].as_str(){
+            x.to_string()
+        }else{
+            panic!("comment_prefix attribute must be a string!");
+        }
+    }else{
+        "#".to_string()
+    };
+
+    let parse = if cfg["parse"].is_badvalue() == false {
+        if let Some(x) = cfg["parse"].as_str(){
+            match x {
+                "yaml" => Some(x.to_string()),
+                _ => panic!("unknown parse {} must be yaml", x)
             }
+        }else{
+            panic!("parse attribute must be a string!");
+        }
+    }else{
+        None
+    };
+
+    EditorRules{ strip_comments, comment_prefix, parse }
+}
+
+// the edited text without its comment lines, checked by the validators and parsed
+// when the question has parse
+fn editor_answer(cfg:&Yaml, rules:&EditorRules, val:&str)-> Result<Yaml, String>{
+    let text = if rules.strip_comments {
+        strip_comment_lines(val, &rules.comment_prefix)
+    }else{
+        val.to_string()
+    };
+    validate_string(cfg, &text)?;
+    match &rules.parse {
+        Some(format) => parse_document(&text, format),
+        None => Ok(string_array(vec![text]))
+    }
+}
+
+// drops the instruction lines and the blank lines left at the end, git commit style
+fn strip_comment_lines(val:&str, comment_prefix:&str)-> String{
+    let lines: Vec<&str> = val
+        .lines()
+        .filter(|l| l.starts_with(comment_prefix) == false)
+        .collect();
+    let mut text = lines.join("\n");
+    text.truncate(text.trim_end().len());
+    text
+}
+
+// the text of a parsed answer, without the document start marker
+fn document_text(val:&Yaml)-> String{
+    let mut out_str = String::new();
+    let mut emitter = YamlEmitter::new(&mut out_str);
+    emitter.dump(val).unwrap();
+    let text = out_str.strip_prefix("---").unwrap_or(&out_str);
+    format!("{}\n", text.strip_prefix(|c| c == ' ' || c == '\n').unwrap_or(text))
+}
+
+fn parse_document(text:&str, format:&str)-> Result<Yaml, String>{
+    match YamlLoader::load_from_str(text) {
+        Ok(docs) => Ok(docs.into_iter().next().unwrap_or(Yaml::Null)),
+        Err(e) => Err(tr("The answer is not valid {}: {}", &[&format, &e]))
+    }
+}
+
+
+fn multiline(_args:&Args, cfg:&Yaml)-> Vec<String>{
+    let msg = if let Some(msg) = cfg["message"].as_str(){
+        msg
+    }else {
+        panic!("Error: A message is required to inquired.");
+    };
+
+    let terminator = if cfg["terminator"].is_badvalue() == false {
+        if let Some(x) = cfg["terminator"].as_str(){
+            x
+        }else{
+            panic!("terminator attribute must be a string!");
+        }
+    }else{
+        "."
+    };
+
+    let min_lines = if cfg["min_lines"].is_badvalue() == false {
+        if let Some(x) = cfg["min_lines"].as_i64(){
+            usize::try_from(x).unwrap()
+        }else{
+            panic!("min_lines attribute must be a i64!");
+        }
+    }else{
+        0
+    };
+
+    let max_lines = if cfg["max_lines"].is_badvalue() == false {
+        if let Some(x) = cfg["max_lines"].as_i64(){
+            usize::try_from(x).unwrap()
+        }else{
+            panic!("max_lines attribute must be a i64!");
+        }
+    }else{
+        usize::MAX
+    };
+
+    let mut predefined: Vec<String> = Vec::new();
+    if cfg["predefined_text"].is_badvalue() == false {
+        if let Some(x) = cfg["predefined_text"].as_str(){
+            predefined = x.lines().map(String::from).collect();
+        }else{
+            panic!("predefined_text attribute must be a string!");
+        };
+    }
+
+    let skippable = if cfg["skippable"].is_badvalue() == false {
+        if let Some(x) = cfg["skippable"].as_bool(){
+            x
         }else{
             panic!("skippable attribute must be a bool!");
         }
     }else{
-        vec![inq.prompt().unwrap()]
+        false
+    };
+
+    if let Some(x) = cfg["help"].as_str(){
+        println!("{} ({})", msg, x);
+    }else{
+        println!("{}", msg);
     }
 
+    loop {
+        let mut lines: Vec<String> = Vec::new();
+        while lines.len() < max_lines {
+            let count = lines.len();
+            let line_msg = format!("{:>3} |", count + 1);
+            let help = if max_lines == usize::MAX {
+                tr("A line with only \"{}\" or esc to finish", &[&terminator])
+            }else{
+                tr("A line with only \"{}\" or esc to finish, {} of {} lines", &[&terminator, &(count + 1), &max_lines])
+            };
+            let line_validator = move |val :&str| {
+                if val == terminator && count < min_lines {
+                    Err(tr("At least {} lines are required", &[&min_lines]))
+                }else{
+                    Ok(())
+                }
+            };
+
+            let mut inq = Text::new(&line_msg)
+                .with_help_message(&help)
+                .with_validator(&line_validator);
+            if let Some(x) = predefined.get(count) {
+                inq = inq.with_initial_value(x);
+            }
+
+            match inq.prompt_skippable().unwrap() {
+                Some(line) if line != terminator => lines.push(line),
+                _ => {
+                    if count < min_lines && (count > 0 || skippable == false) {
+                        println!("{}", tr("At least {} lines are required", &[&min_lines]));
+                        continue;
+                    }
+                    break;
+                }
+            }
+        }
+
+        if lines.is_empty() && skippable {
+            return Vec::new();
+        }
+
+        let text = lines.join("\n");
+        match validate_string(cfg, &text) {
+            Ok(()) => return vec![text],
+            Err(e) => {
+                println!("{}", e);
+                predefined = lines;
+            }
+        }
+    }
 }
 
 
 
-fn date_select(args:&Args, cfg:&Yaml)-> Vec<String>{
+fn date_select(_args:&Args, cfg:&Yaml)-> Result<Vec<String>, GoBack>{
     let mut inq = if let Some(msg) = cfg["message"].as_str(){
         DateSelect::new(msg)
     }else {
@@ -579,103 +3674,601 @@ fn date_select(args:&Args, cfg:&Yaml)-> Vec<String>{
     // }else{
     //     vec![&inq.prompt().unwrap().format("%Y-%m-%d").to_string()]
     // }
-    vec![inq.prompt().unwrap().format("%Y-%m-%d").to_string()]
+    Ok(vec![prompted(inq.prompt())?.format("%Y-%m-%d").to_string()])
+}
+
+
+
+// an option of select, multi_select and rank: either a string or a hash with a
+// value that is stored, a label that is shown and optionally a group and a description
+#[derive(Clone, PartialEq)]
+struct Choice {
+    value       : String,
+    label       : String,
+    group       : Option<String>,
+    description : Option<String>,
+    kind        : ChoiceKind,
+    // shown but not selectable, with the reason if any
+    disabled    : Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ChoiceKind {
+    Option,
+    // a group name shown before its options, it can not be picked
+    Heading,
+    // the multi_select entry checking every available option or none
+    All,
+    // the allow_other entry asking for a free text answer
+    Other,
+}
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f:&mut fmt::Formatter)-> fmt::Result{
+        match self.kind {
+            ChoiceKind::Heading => return write!(f, "── {} ──", self.label),
+            ChoiceKind::All | ChoiceKind::Other => return write!(f, "{}", self.label),
+            ChoiceKind::Option => {}
+        }
+        if self.group.is_some() {
+            write!(f, "  ")?;
+        }
+        write!(f, "{}", self.label)?;
+        match &self.disabled {
+            Some(r) if r.is_empty() => write!(f, " ({})", tr("unavailable", &[])),
+            Some(r) => write!(f, " ({}: {})", tr("unavailable", &[]), r),
+            None => Ok(())
+        }
+    }
+}
+
+impl Choice {
+    fn unavailable(&self)-> String{
+        match &self.disabled {
+            Some(r) if r.is_empty() == false => tr("{} is not available: {}", &[&self.label, r]),
+            _ => tr("{} is not available", &[&self.label])
+        }
+    }
+}
+
+fn choices(cfg:&Yaml)-> Vec<Choice>{
+    let options = if cfg["options"].is_badvalue() == false {
+        if let Some(x) = cfg["options"].as_vec(){
+            x
+        }else{
+            panic!("options attribute must be an array!");
+        }
+    }else{
+        panic!("options are required!");
+    };
+
+    let optional_string = |o:&Yaml, attribute:&str| {
+        if o[attribute].is_badvalue() == false {
+            if let Some(x) = o[attribute].as_str(){
+                Some(x.to_string())
+            }else{
+                panic!("option {} attribute must be a string!", attribute);
+            }
+        }else{
+            None
+        }
+    };
+
+    options.iter().map(|o| {
+        if let Some(x) = o.as_str() {
+            return Choice{ value: x.to_string(), label: x.to_string(), group: None, description: None, kind: ChoiceKind::Option, disabled: None };
+        }
+        if o.as_hash().is_none() {
+            panic!("options must be strings or hashes with a value and a label!");
+        }
+        let value = if let Some(x) = o["value"].as_str(){
+            x.to_string()
+        }else{
+            panic!("option value attribute must be a string!");
+        };
+        let disabled = match &o["disabled"] {
+            Yaml::BadValue => false,
+            Yaml::Boolean(x) => *x,
+            _ => panic!("option disabled attribute must be a bool or a when expression!")
+        };
+        Choice{
+            label       : optional_string(o, "label").unwrap_or_else(|| value.clone()),
+            value,
+            group       : optional_string(o, "group"),
+            description : optional_string(o, "description"),
+            kind        : ChoiceKind::Option,
+            disabled    : if disabled { Some(optional_string(o, "reason").unwrap_or_default()) } else { None },
+        }
+    }).collect()
+}
+
+// the options with a heading before each run of options sharing a group
+fn with_headings(options:Vec<Choice>)-> Vec<Choice>{
+    let mut shown = Vec::new();
+    let mut current: Option<String> = None;
+    for o in options {
+        if o.group.is_some() && o.group != current {
+            shown.push(Choice{
+                value       : String::new(),
+                label       : o.group.clone().unwrap(),
+                group       : None,
+                description : None,
+                kind        : ChoiceKind::Heading,
+                disabled    : None,
+            });
+        }
+        current = o.group.clone();
+        shown.push(o);
+    }
+    shown
+}
+
+// the position in the shown list of the n-th option, defaults and starting_cursor
+// count options only so headings do not shift them
+fn shown_index(shown:&[Choice], index:usize)-> usize{
+    match shown.iter().enumerate().filter(|(_, o)| o.kind == ChoiceKind::Option).nth(index) {
+        Some((i, _)) => i,
+        None => panic!("option index {} is out of range!", index)
+    }
+}
+
+// the options of a question, empty for questions without options
+fn option_choices(cfg:&Yaml)-> Vec<Choice>{
+    if cfg["options"].as_vec().is_none() {
+        return Vec::new();
+    }
+    choices(cfg)
+}
+
+// the allow_other settings, true or a hash with label, message, placeholder,
+// validators and mask for the free text answer
+fn other_cfg(cfg:&Yaml)-> Option<Yaml>{
+    match &cfg["allow_other"] {
+        Yaml::BadValue | Yaml::Boolean(false) => None,
+        Yaml::Boolean(true) => Some(Yaml::Hash(LinkedHashMap::new())),
+        Yaml::Hash(_) => Some(cfg["allow_other"].clone()),
+        _ => panic!("allow_other attribute must be a bool or a hash!")
+    }
+}
+
+fn other_choice(other:&Yaml)-> Choice{
+    let label = if other["label"].is_badvalue() == false {
+        if let Some(x) = other["label"].as_str(){
+            x.to_string()
+        }else{
+            panic!("allow_other label attribute must be a string!");
+        }
+    }else{
+        tr("Other…", &[])
+    };
+    Choice{ value: String::new(), label, group: None, description: None, kind: ChoiceKind::Other, disabled: None }
+}
+
+// the free text typed after picking the allow_other entry
+fn other_text(other:&Yaml)-> Result<String, GoBack>{
+    let default_msg = tr("Please specify:", &[]);
+    let msg = if other["message"].is_badvalue() == false {
+        if let Some(x) = other["message"].as_str(){
+            x
+        }else{
+            panic!("allow_other message attribute must be a string!");
+        }
+    }else{
+        &default_msg
+    };
+
+    let mut inq = Text::new(msg);
+    if other["placeholder"].is_badvalue() == false {
+        inq = if let Some(x) = other["placeholder"].as_str(){
+            inq.with_placeholder(x)
+        }else{
+            panic!("allow_other placeholder attribute must be a string!");
+        };
+    }else if let Some(x) = other["mask"].as_str(){
+        inq = inq.with_placeholder(mask_example(x));
+    }
+
+    let validator = |val :&str| {
+        if val.trim().is_empty() {
+            return Err(tr("A value is required", &[]));
+        }
+        validate_mask(other, val).and_then(|_| validate_string(other, val))
+    };
+    inq = inq.with_validator(&validator);
+
+    prompted(inq.prompt())
+}
+
+fn select(_args:&Args, cfg:&Yaml)-> Result<Vec<String>, GoBack>{
+    let msg = if let Some(msg) = cfg["message"].as_str(){
+        msg
+    }else {
+        panic!("Error: A message is required to inquired.");
+    };
+    let other = other_cfg(cfg);
+    let mut options = with_headings(choices(cfg));
+    if let Some(o) = &other {
+        options.push(other_choice(o));
+    }
+
+    let mut cursor = if cfg["starting_cursor"].is_badvalue() == false {
+        if let Some(x) = cfg["starting_cursor"].as_i64(){
+            shown_index(&options, x.try_into().unwrap())
+        }else{
+            panic!("startings_cursor attribute must be a i64!");
+        }
+    }else{
+        shown_index(&options, 0)
+    };
+
+    let skippable = if cfg["skippable"].is_badvalue() == false {
+        if let Some(x) = cfg["skippable"].as_bool(){
+            x
+        }else{
+            panic!("skippable attribute must be a bool!");
+        }
+    }else{
+        false
+    };
+
+    let formatter = |o:ListOption<&Choice>| o.value.label.clone();
+    let described = options.iter().any(|o| o.description.is_some());
+
+    // select has no validator, picking a heading asks again from the option below it
+    loop {
+        let mut inq = Select::new(msg, options.clone())
+            .with_starting_cursor(cursor)
+            .with_formatter(&formatter);
+
+        if cfg["help"].is_badvalue() == false {
+            inq = if let Some(x) = cfg["help"].as_str(){
+                inq.with_help_message(x)
+            }else{
+                panic!("help attribute must be a string!");
+            };
+        }
+
+        if cfg["page_size"].is_badvalue() == false {
+            inq = if let Some(x) = cfg["page_size"].as_i64(){
+                inq.with_page_size(x.try_into().unwrap())
+            }else{
+                panic!("page_size attribute must be a i64!");
+            };
+        }
+
+        let answer = if described {
+            let help = cfg["help"].as_str().map(String::from).unwrap_or_else(|| tr("↑↓ to move, enter to select, type to filter", &[]));
+            described_list(cfg, msg, &help, &options, cursor, None, &|picked| Ok(options[picked[0]].label.clone()))
+                .map(|picked| options[picked[0]].clone())
+        }else{
+            inq.prompt()
+        };
+        let answer = if skippable {
+            match answer {
+                Err(InquireError::OperationCanceled) => return Ok(Vec::new()),
+                _ => answer.unwrap()
+            }
+        }else{
+            prompted(answer)?
+        };
+
+        if answer.kind == ChoiceKind::Heading {
+            cursor = options.iter().position(|o| o == &answer).unwrap() + 1;
+            continue;
+        }
+        if answer.disabled.is_some() {
+            println!("{}", answer.unavailable());
+            cursor = options.iter().position(|o| o == &answer).unwrap();
+            continue;
+        }
+        // the free text is stored instead of an option, esc on it picks again
+        if answer.kind == ChoiceKind::Other {
+            cursor = options.iter().position(|o| o == &answer).unwrap();
+            match other_text(other.as_ref().unwrap()) {
+                Ok(x) => return Ok(vec![x]),
+                Err(GoBack) => continue
+            }
+        }
+        return Ok(vec![answer.value]);
+    }
+}
+
+// the indexes of the options checked by default, given as option indexes, option
+// values or "all", disabled options are left out
+fn multi_select_defaults(cfg:&Yaml, options:&[Choice])-> Vec<usize>{
+    let indexes: Vec<usize> = match &cfg["default"] {
+        Yaml::BadValue => Vec::new(),
+        Yaml::String(x) if x == "all" => (0..options.len()).collect(),
+        Yaml::Array(x) => x.iter().map(|a| match a {
+            Yaml::Integer(i) => {
+                let i = usize::try_from(*i).unwrap();
+                if i >= options.len() {
+                    panic!("default index {} is out of range!", i);
+                }
+                i
+            },
+            Yaml::String(v) => match options.iter().position(|o| &o.value == v) {
+                Some(i) => i,
+                None => panic!("default value {} is not one of the options!", v)
+            },
+            _ => panic!("default attribute must be an array of option indexes or values, or all!")
+        }).collect(),
+        _ => panic!("default attribute must be an array of option indexes or values, or all!")
+    };
+    indexes.into_iter().filter(|&i| options[i].disabled.is_none()).collect()
 }
 
+// the options a submit stands for: the select all entry checks every available
+// option, or none when they are all checked already
+fn checked_options<'a>(picked:&[&'a Choice], shown:&'a [Choice])-> Vec<&'a Choice>{
+    let checked: Vec<&Choice> = picked.iter().copied().filter(|o| o.kind != ChoiceKind::All).collect();
+    if picked.iter().any(|o| o.kind == ChoiceKind::All) == false {
+        return checked;
+    }
+    let available: Vec<&Choice> = shown.iter().filter(|o| o.kind == ChoiceKind::Option && o.disabled.is_none()).collect();
+    // select all only toggles the options, a checked other entry stays either way
+    let other = checked.iter().copied().filter(|o| o.kind == ChoiceKind::Other);
+    if available.iter().all(|o| checked.contains(o)) {
+        other.collect()
+    }else{
+        available.into_iter().chain(other).collect()
+    }
+}
+
+// inquire only shows a fixed help message, so a select or multi_select with option
+// descriptions is drawn here with the description of the highlighted option in the
+// help line, help is shown for the others. the keys, paging and filter are the
+// inquire ones, checked is Some for a multi_select. submit validates the picked
+// indexes and gives the echoed answer, the picked indexes are returned
+fn described_list(cfg:&Yaml, msg:&str, help:&str, options:&[Choice], start:usize, checked:Option<Vec<usize>>,
+                  submit:&dyn Fn(&[usize])-> Result<String, String>)-> InquireResult<Vec<usize>>{
+    let config = render_config(cfg);
+    let multi = checked.is_some();
+    let mut checked = checked.unwrap_or_default();
+    let page_size = cfg["page_size"].as_i64().map(|x| usize::try_from(x).unwrap()).unwrap_or(7);
+    let keep_filter = cfg["keep_filter"].as_bool().unwrap_or(true);
+    let shown: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+
+    let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+    let mut keys = tty.try_clone()?.keys();
+    let mut out = tty.into_raw_mode()?;
+    let title = format!("{} {}", painted(&config.prompt_prefix), painted(&Styled::new(msg).with_style_sheet(config.prompt)));
+
+    let mut filter = String::new();
+    let mut filtered: Vec<usize> = (0..options.len()).collect();
+    let mut cursor = start;
+    let mut error: Option<String> = None;
+    // the line of the previous frame the cursor was left on
+    let mut row = 0;
+    loop {
+        let mut lines = Vec::new();
+        if let Some(e) = &error {
+            lines.push(format!("{} {}", painted(&config.error_message.prefix), painted(&Styled::new(e).with_style_sheet(config.error_message.message))));
+        }
+        let prompt_row = lines.len();
+        lines.push(format!("{} {}", title, filter));
+
+        // the page around the cursor, like the inquire paginate
+        let (first, last) = if filtered.len() <= page_size {
+            (0, filtered.len())
+        }else if cursor < page_size / 2 {
+            (0, page_size)
+        }else if filtered.len() - cursor - 1 < page_size / 2 {
+            (filtered.len() - page_size, filtered.len())
+        }else{
+            (cursor - page_size / 2, cursor - page_size / 2 + page_size)
+        };
+        for (n, &i) in filtered[first..last].iter().enumerate() {
+            let prefix = if first + n == cursor {
+                painted(&config.highlighted_option_prefix)
+            }else if n == 0 && first > 0 {
+                painted(&config.scroll_up_prefix)
+            }else if first + n + 1 == last && last < filtered.len() {
+                painted(&config.scroll_down_prefix)
+            }else{
+                " ".to_string()
+            };
+            let checkbox = if multi == false {
+                String::new()
+            }else if checked.contains(&i) {
+                format!("{} ", painted(&config.selected_checkbox))
+            }else{
+                format!("{} ", painted(&config.unselected_checkbox))
+            };
+            lines.push(format!("{} {}{}", prefix, checkbox, painted(&Styled::new(&shown[i]).with_style_sheet(config.option))));
+        }
+
+        let line = match filtered.get(cursor).and_then(|&i| options[i].description.as_ref()) {
+            Some(d) => d,
+            None => help
+        };
+        lines.push(painted(&Styled::new(format!("[{}]", line)).with_style_sheet(config.help_message)));
 
+        if row > 0 {
+            write!(out, "{}", termion::cursor::Up(row))?;
+        }
+        write!(out, "\r{}{}", clear::AfterCursor, lines.join("\r\n"))?;
+        // the cursor is left after the filter
+        let up = lines.len() - 1 - prompt_row;
+        if up > 0 {
+            write!(out, "{}", termion::cursor::Up(u16::try_from(up).unwrap()))?;
+        }
+        let column = config.prompt_prefix.content.chars().count() + msg.chars().count() + filter.chars().count() + 2;
+        write!(out, "\r{}", termion::cursor::Right(u16::try_from(column).unwrap()))?;
+        out.flush()?;
+        row = u16::try_from(prompt_row).unwrap();
 
-fn select(args:&Args, cfg:&Yaml)-> Vec<String>{
-    let mut inq = if let Some(msg) = cfg["message"].as_str(){
-        let mut options = Vec::new();
-        if cfg["options"].is_badvalue() == false {
-            if let Some(x) = cfg["options"].as_vec(){
-                for a in x {
-                    options.push(a.as_str().unwrap());
+        let key = match keys.next() {
+            Some(k) => k?,
+            None => return Err(InquireError::NotTTY)
+        };
+        let finish = |out:&mut termion::raw::RawTerminal<File>, answer:String| {
+            let up = if row > 0 { termion::cursor::Up(row).to_string() } else { String::new() };
+            write!(out, "{}\r{}{} {}\r\n", up, clear::AfterCursor, title, answer)
+        };
+        match key {
+            Key::Char('\n') | Key::Char('\r') => {
+                let picked = if multi {
+                    checked.clone()
+                }else{
+                    match filtered.get(cursor) {
+                        Some(&i) => vec![i],
+                        None => continue
+                    }
+                };
+                match submit(&picked) {
+                    Ok(answer) => {
+                        finish(&mut out, painted(&Styled::new(answer).with_style_sheet(config.answer)))?;
+                        return Ok(picked);
+                    },
+                    Err(e) => {
+                        error = Some(e);
+                        continue;
+                    }
                 }
-                Select::new(msg, options)
-            }else{
-                panic!("help attribute must be a string!");
-            }
-        }else{
-            panic!("options are required!");
+            },
+            Key::Esc | Key::Ctrl('c') => {
+                finish(&mut out, painted(&config.canceled_prompt_indicator))?;
+                return if key == Key::Esc { Err(InquireError::OperationCanceled) } else { Err(InquireError::OperationInterrupted) };
+            },
+            Key::Up => cursor = if cursor == 0 { filtered.len().saturating_sub(1) } else { cursor - 1 },
+            Key::Down => cursor = if cursor + 1 >= filtered.len() { 0 } else { cursor + 1 },
+            Key::PageUp => cursor = cursor.saturating_sub(page_size),
+            Key::PageDown => cursor = (cursor + page_size).min(filtered.len().saturating_sub(1)),
+            Key::Home => cursor = 0,
+            Key::End => cursor = filtered.len().saturating_sub(1),
+            Key::Char(' ') if multi => {
+                if let Some(&i) = filtered.get(cursor) {
+                    match checked.iter().position(|&c| c == i) {
+                        Some(n) => {
+                            checked.remove(n);
+                        },
+                        None => checked.push(i)
+                    }
+                    checked.sort();
+                }
+                if keep_filter == false {
+                    filter.clear();
+                }
+            },
+            Key::Right if multi => {
+                checked = filtered.clone();
+                if keep_filter == false {
+                    filter.clear();
+                }
+            },
+            Key::Left if multi => {
+                checked.clear();
+                if keep_filter == false {
+                    filter.clear();
+                }
+            },
+            Key::Char(c) => filter.push(c),
+            Key::Backspace => {
+                filter.pop();
+            },
+            _ => continue
         }
+        let lower = filter.to_lowercase();
+        filtered = (0..options.len()).filter(|&i| shown[i].to_lowercase().contains(&lower)).collect();
+        if filtered.is_empty() == false && cursor >= filtered.len() {
+            cursor = filtered.len() - 1;
+        }
+    }
+}
+
+fn multi_select(_args:&Args, cfg:&Yaml)-> Result<Vec<String>, GoBack>{
+    let msg = if let Some(msg) = cfg["message"].as_str(){
+        msg
     }else {
         panic!("Error: A message is required to inquired.");
     };
 
-    if cfg["help"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["help"].as_str(){
-            inq.with_help_message(x)
+    let choices = choices(cfg);
+    let mut options = Vec::new();
+    if cfg["select_all"].is_badvalue() == false {
+        if let Some(x) = cfg["select_all"].as_bool(){
+            if x {
+                options.push(Choice{
+                    value       : String::new(),
+                    label       : tr("Select all / none", &[]),
+                    group       : None,
+                    description : None,
+                    kind        : ChoiceKind::All,
+                    disabled    : None,
+                });
+            }
         }else{
-            panic!("help attribute must be a string!");
-        };
+            panic!("select_all attribute must be a bool!");
+        }
     }
-
-    if cfg["page_size"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["page_size"].as_i64(){
-            inq.with_page_size(x.try_into().unwrap())
-        }else{
-            panic!("page_size attribute must be a i64!");
-        };
+    options.extend(with_headings(choices.clone()));
+    let other = other_cfg(cfg);
+    if let Some(o) = &other {
+        options.push(other_choice(o));
     }
 
+    let min = if cfg["min"].is_badvalue() == false {
+        if let Some(x) = cfg["min"].as_i64(){
+            usize::try_from(x).unwrap()
+        }else{
+            panic!("min attribute must be a i64!");
+        }
+    }else{
+        0
+    };
 
-    if cfg["starting_cursor"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["starting_cursor"].as_i64(){
-            inq.with_starting_cursor(x.try_into().unwrap())
+    let max = if cfg["max"].is_badvalue() == false {
+        if let Some(x) = cfg["max"].as_i64(){
+            usize::try_from(x).unwrap()
         }else{
-            panic!("startings_cursor attribute must be a i64!");
-        };
+            panic!("max attribute must be a i64!");
+        }
+    }else{
+        usize::MAX
+    };
+    if min > max {
+        panic!("min can not be greater than max!");
     }
 
-    if cfg["skippable"].is_badvalue() == false {
+    let skippable = if cfg["skippable"].is_badvalue() == false {
         if let Some(x) = cfg["skippable"].as_bool(){
-            if x {
-                if let Some(r) = inq.prompt_skippable().unwrap(){
-                    vec![r.to_string()]
-                }else{
-                    Vec::new()
-                }
-            }else{
-                vec![inq.prompt().unwrap().to_string()]
-            }
+            x
         }else{
             panic!("skippable attribute must be a bool!");
         }
     }else{
-        vec![inq.prompt().unwrap().to_string()]
-    }
-}
+        false
+    };
 
-fn multi_select(args:&Args, cfg:&Yaml)-> Vec<String>{
-    let mut inq = if let Some(msg) = cfg["message"].as_str(){
-        let mut options = Vec::new();
-        if cfg["options"].is_badvalue() == false {
-            if let Some(x) = cfg["options"].as_vec(){
-                for a in x {
-                    options.push(a.as_str().unwrap());
-                }
-                MultiSelect::new(msg, options)
-            }else{
-                panic!("help attribute must be a string!");
-            }
+    let mut inq = MultiSelect::new(msg, options.clone());
+
+    let check = |picked:&[&Choice]| {
+        if let Some(o) = picked.iter().find(|o| o.kind == ChoiceKind::Heading) {
+            return Err(tr("{} is a group heading, pick its options instead", &[&o.label]));
+        }
+        if let Some(o) = picked.iter().find(|o| o.disabled.is_some()) {
+            return Err(o.unavailable());
+        }
+        let count = checked_options(picked, &options).len();
+        if count < min {
+            Err(tr("At least {} options must be selected", &[&min]))
+        }else if count > max {
+            Err(tr("At most {} options can be selected", &[&max]))
         }else{
-            panic!("options are required!");
+            Ok(())
         }
-    }else {
-        panic!("Error: A message is required to inquired.");
     };
+    let echo = |picked:&[&Choice]| {
+        checked_options(picked, &options).iter().map(|o| o.label.clone()).collect::<Vec<String>>().join(", ")
+    };
+    let validator = |selected:&[ListOption<&Choice>]| check(&selected.iter().map(|o| o.value).collect::<Vec<&Choice>>());
+    let formatter = |selected:&[ListOption<&Choice>]| echo(&selected.iter().map(|o| o.value).collect::<Vec<&Choice>>());
+    inq = inq.with_validator(&validator).with_formatter(&formatter);
 
-    let mut defaults = Vec::new();
-    if cfg["default"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["default"].as_vec(){
-            for a in x {
-                defaults.push(usize::try_from(a.as_i64().unwrap()).unwrap());
-            }
-            inq.with_default(&defaults)
-        }else{
-            panic!("default attribute must be a string!");
-        };
+    let defaults: Vec<usize> = multi_select_defaults(cfg, &choices).into_iter().map(|i| shown_index(&options, i)).collect();
+    if defaults.is_empty() == false {
+        inq = inq.with_default(&defaults);
     }
 
     if cfg["help"].is_badvalue() == false {
@@ -695,56 +4288,844 @@ fn multi_select(args:&Args, cfg:&Yaml)-> Vec<String>{
     }
 
 
-    if cfg["starting_cursor"].is_badvalue() == false {
-        inq = if let Some(x) = cfg["starting_cursor"].as_i64(){
-            inq.with_starting_cursor(x.try_into().unwrap())
+    let starting_cursor = if cfg["starting_cursor"].is_badvalue() == false {
+        if let Some(x) = cfg["starting_cursor"].as_i64(){
+            shown_index(&options, x.try_into().unwrap())
         }else{
             panic!("startings_cursor attribute must be a i64!");
-        };
-    }
+        }
+    }else{
+        0
+    };
+    inq = inq.with_starting_cursor(starting_cursor);
 
         if cfg["keep_filter"].is_badvalue() == false {
         inq = if let Some(x) = cfg["keep_filter"].as_bool(){
-            inq.with_keep_filter(x.try_into().unwrap())
+            inq.with_keep_filter(x)
         }else{
             panic!("keep_filter attribute must be a bool!");
         };
     }
 
-    if cfg["skippable"].is_badvalue() == false {
-        if let Some(x) = cfg["skippable"].as_bool(){
-            if x {
-                if let Some(r) = inq.prompt_skippable().unwrap(){
-                    let mut v = Vec::new();
-                    for x in r{
-                        v.push(x.to_string());
-                    }
-                    v
-                    
-                }else{
-                    Vec::new()
-                }
+    let described = options.iter().any(|o| o.description.is_some());
+    let chosen = |picked:&[usize]| picked.iter().map(|&i| &options[i]).collect::<Vec<&Choice>>();
+
+    let mut checked: Option<Vec<usize>> = None;
+    loop {
+        // after esc on the free text the options picked before are checked again
+        let mut inq = inq.clone();
+        if let Some(x) = &checked {
+            inq = inq.with_default(x);
+        }
+
+        let answer = if described {
+            let start = checked.clone().unwrap_or_else(|| defaults.clone());
+            let help = cfg["help"].as_str().map(String::from)
+                .unwrap_or_else(|| tr("↑↓ to move, space to select one, → to all, ← to none, type to filter", &[]));
+            described_list(cfg, msg, &help, &options, starting_cursor, Some(start), &|picked| check(&chosen(picked)).map(|_| echo(&chosen(picked))))
+                .map(|picked| picked.into_iter().map(|i| options[i].clone()).collect::<Vec<Choice>>())
+        }else{
+            inq.prompt()
+        };
+        let answer = if skippable {
+            match answer {
+                Err(InquireError::OperationCanceled) => return Ok(Vec::new()),
+                _ => answer.unwrap()
+            }
+        }else{
+            prompted(answer)?
+        };
+
+        // the free text is stored alongside the checked options
+        let picked: Vec<&Choice> = answer.iter().collect();
+        let values: Result<Vec<String>, GoBack> = checked_options(&picked, &options).into_iter().map(|o| {
+            if o.kind == ChoiceKind::Other {
+                other_text(other.as_ref().unwrap())
             }else{
-                let mut v = Vec::new();
-                for x in inq.prompt().unwrap(){
-                    v.push(x.to_string());
+                Ok(o.value.clone())
+            }
+        }).collect();
+        match values {
+            Ok(x) => return Ok(x),
+            Err(GoBack) => {
+                checked = Some(answer.iter().map(|a| options.iter().position(|o| o == a).unwrap()).collect());
+            }
+        }
+    }
+}
+
+fn rank(_args:&Args, cfg:&Yaml)-> Result<Vec<String>, GoBack>{
+    let msg = if let Some(msg) = cfg["message"].as_str(){
+        msg
+    }else {
+        panic!("Error: A message is required to inquired.");
+    };
+
+    let options = choices(cfg);
+    let values: Vec<String> = options.iter().map(|o| o.value.clone()).collect();
+
+    // the default order, options missing from it keep their place at the end
+    let mut default_order = Vec::new();
+    if cfg["default"].is_badvalue() == false {
+        if let Some(x) = cfg["default"].as_vec(){
+            for a in x {
+                let v = a.as_str().unwrap().to_string();
+                if values.contains(&v) == false {
+                    panic!("default value {} is not one of the options!", v);
                 }
-                v
+                default_order.push(v);
             }
+        }else{
+            panic!("default attribute must be an array!");
+        };
+    }
+    for v in &values {
+        if default_order.contains(v) == false {
+            default_order.push(v.clone());
+        }
+    }
+
+    let limit = if cfg["limit"].is_badvalue() == false {
+        if let Some(x) = cfg["limit"].as_i64(){
+            usize::try_from(x).unwrap().min(options.len())
+        }else{
+            panic!("limit attribute must be a i64!");
+        }
+    }else{
+        options.len()
+    };
+
+    let skippable = if cfg["skippable"].is_badvalue() == false {
+        if let Some(x) = cfg["skippable"].as_bool(){
+            x
         }else{
             panic!("skippable attribute must be a bool!");
         }
     }else{
-        let mut v = Vec::new();
-        for x in inq.prompt().unwrap(){
-            v.push(x.to_string());
+        false
+    };
+
+    let default_help = tr("↑↓ to move, enter to pick the next item in order", &[]);
+    let help = if cfg["help"].is_badvalue() == false {
+        if let Some(x) = cfg["help"].as_str(){
+            x
+        }else{
+            panic!("help attribute must be a string!");
+        }
+    }else{
+        &default_help
+    };
+
+    let mut ranked: Vec<String> = Vec::new();
+    let mut remaining = options.clone();
+    while ranked.len() < limit {
+        if remaining.len() == 1 {
+            ranked.push(remaining.remove(0).value);
+            break;
+        }
+
+        let next_default = default_order.iter().find(|o| ranked.contains(o) == false).unwrap();
+        let cursor = remaining.iter().position(|o| &o.value == next_default).unwrap();
+
+        let pick_msg = tr("{} (#{})", &[&msg, &(ranked.len() + 1)]);
+        let mut inq = Select::new(&pick_msg, remaining.clone())
+            .with_starting_cursor(cursor)
+            .with_help_message(help);
+
+        if cfg["page_size"].is_badvalue() == false {
+            inq = if let Some(x) = cfg["page_size"].as_i64(){
+                inq.with_page_size(x.try_into().unwrap())
+            }else{
+                panic!("page_size attribute must be a i64!");
+            };
         }
-        v
+
+        let answer = if remaining.iter().any(|o| o.description.is_some()) {
+            described_list(cfg, &pick_msg, help, &remaining, cursor, None, &|picked| Ok(remaining[picked[0]].to_string()))
+                .map(|picked| remaining[picked[0]].clone())
+        }else{
+            inq.prompt()
+        };
+        let choice = if skippable {
+            match answer {
+                Err(InquireError::OperationCanceled) => return Ok(Vec::new()),
+                _ => answer.unwrap()
+            }
+        }else{
+            match prompted(answer) {
+                Ok(r) => r,
+                // esc takes back the last pick, on the first one it leaves the question
+                Err(GoBack) => {
+                    ranked.pop().ok_or(GoBack)?;
+                    remaining = options.iter().filter(|o| ranked.contains(&o.value) == false).cloned().collect();
+                    continue;
+                }
+            }
+        };
+
+        remaining.retain(|o| o != &choice);
+        ranked.push(choice.value);
     }
+    Ok(ranked)
+}
 
-    
+
+// the built-in messages of a language, keyed by their english text
+fn builtin_translations(lang:&str)-> Vec<(&'static str, &'static str)>{
+    let column = match lang {
+        "es" => 1,
+        "fr" => 2,
+        "de" => 3,
+        _ => return Vec::new()
+    };
+    TRANSLATED_MESSAGES.iter().map(|m| (m[0], m[column])).collect()
 }
 
+// english, spanish, french, german
+const TRANSLATED_MESSAGES: &[[&str; 4]] = &[
+    ["Unknown type \"{}\" must be either text, path, list, map, group, multiline, editor, date_select, select, multi_select, rank, confirm, password, note, computed !",
+     "Tipo \"{}\" desconocido, debe ser text, path, list, map, group, multiline, editor, date_select, select, multi_select, rank, confirm, password, note o computed !",
+     "Type \"{}\" inconnu, doit être text, path, list, map, group, multiline, editor, date_select, select, multi_select, rank, confirm, password, note ou computed !",
+     "Unbekannter Typ \"{}\", erlaubt sind text, path, list, map, group, multiline, editor, date_select, select, multi_select, rank, confirm, password, note oder computed !"],
+    ["Unknown variable \"{}\" in template \"{}\" must be a previous answer, an answer in the answers file, hostname or env.NAME",
+     "Variable \"{}\" desconocida en la plantilla \"{}\", debe ser una respuesta anterior, una respuesta del archivo de respuestas, hostname o env.NOMBRE",
+     "Variable \"{}\" inconnue dans le modèle \"{}\", doit être une réponse précédente, une réponse du fichier de réponses, hostname ou env.NOM",
+     "Unbekannte Variable \"{}\" in der Vorlage \"{}\", erlaubt sind frühere Antworten, Antworten aus der Antwortdatei, hostname oder env.NAME"],
+    ["Timed out, the default was used for: {}",
+     "Tiempo agotado, se usó el valor por defecto para: {}",
+     "Délai dépassé, la valeur par défaut a été utilisée pour : {}",
+     "Zeit abgelaufen, der Standardwert wurde verwendet für: {}"],
+    ["(timed out)",
+     "(tiempo agotado)",
+     "(délai dépassé)",
+     "(Zeit abgelaufen)"],
+    ["[using the default in {}s, press any key to answer]",
+     "[se usará el valor por defecto en {}s, pulse una tecla para responder]",
+     "[valeur par défaut dans {}s, appuyez sur une touche pour répondre]",
+     "[Standardwert in {}s, zum Antworten eine Taste drücken]"],
+    ["Question {} of {}",
+     "Pregunta {} de {}",
+     "Question {} sur {}",
+     "Frage {} von {}"],
+    ["Add another?",
+     "¿Añadir otro?",
+     "En ajouter un autre ?",
+     "Weiteren hinzufügen?"],
+    ["Review your answers, pick one to change it",
+     "Revise sus respuestas, elija una para cambiarla",
+     "Vérifiez vos réponses, choisissez-en une pour la modifier",
+     "Antworten prüfen, zum Ändern eine auswählen"],
+    ["↑↓ to move, enter to change the answer",
+     "↑↓ para moverse, enter para cambiar la respuesta",
+     "↑↓ pour se déplacer, entrée pour modifier la réponse",
+     "↑↓ zum Bewegen, Enter zum Ändern der Antwort"],
+    ["Looks good, write the answers",
+     "Todo correcto, guardar las respuestas",
+     "C'est bon, enregistrer les réponses",
+     "Passt, Antworten speichern"],
+    ["Press enter to continue",
+     "Pulse enter para continuar",
+     "Appuyez sur entrée pour continuer",
+     "Enter drücken, um fortzufahren"],
+    ["Type {} or {}",
+     "Escriba {} o {}",
+     "Tapez {} ou {}",
+     "{} oder {} eingeben"],
+    ["\"{}\" is not allowed in a {} value like {}",
+     "\"{}\" no está permitido en un valor {} como {}",
+     "\"{}\" n'est pas autorisé dans une valeur {} comme {}",
+     "\"{}\" ist in einem {}-Wert wie {} nicht erlaubt"],
+    ["The answer must be a valid {} like {}",
+     "La respuesta debe ser un {} válido como {}",
+     "La réponse doit être un {} valide comme {}",
+     "Die Antwort muss ein gültiger {}-Wert wie {} sein"],
+    ["The answer must match {}",
+     "La respuesta debe coincidir con {}",
+     "La réponse doit correspondre à {}",
+     "Die Antwort muss {} entsprechen"],
+    ["The answer must be at least {} characters long",
+     "La respuesta debe tener al menos {} caracteres",
+     "La réponse doit contenir au moins {} caractères",
+     "Die Antwort muss mindestens {} Zeichen lang sein"],
+    ["The answer must be at most {} characters long",
+     "La respuesta debe tener como máximo {} caracteres",
+     "La réponse doit contenir au plus {} caractères",
+     "Die Antwort darf höchstens {} Zeichen lang sein"],
+    ["File {} does not exist",
+     "El archivo {} no existe",
+     "Le fichier {} n'existe pas",
+     "Die Datei {} existiert nicht"],
+    ["Directory {} does not exist",
+     "El directorio {} no existe",
+     "Le répertoire {} n'existe pas",
+     "Das Verzeichnis {} existiert nicht"],
+    ["The directory of {} does not exist",
+     "El directorio de {} no existe",
+     "Le répertoire de {} n'existe pas",
+     "Das Verzeichnis von {} existiert nicht"],
+    ["At least {} items are required",
+     "Se requieren al menos {} elementos",
+     "Au moins {} éléments sont requis",
+     "Mindestens {} Einträge sind erforderlich"],
+    ["Add an entry",
+     "Añadir una entrada",
+     "Ajouter une entrée",
+     "Eintrag hinzufügen"],
+    ["Done",
+     "Terminado",
+     "Terminé",
+     "Fertig"],
+    ["Key:",
+     "Clave:",
+     "Clé :",
+     "Schlüssel:"],
+    ["Value for {}:",
+     "Valor para {}:",
+     "Valeur pour {} :",
+     "Wert für {}:"],
+    ["A key is required",
+     "Se requiere una clave",
+     "Une clé est requise",
+     "Ein Schlüssel ist erforderlich"],
+    ["Key {} already exists",
+     "La clave {} ya existe",
+     "La clé {} existe déjà",
+     "Der Schlüssel {} existiert bereits"],
+    ["The key must match {}",
+     "La clave debe coincidir con {}",
+     "La clé doit correspondre à {}",
+     "Der Schlüssel muss {} entsprechen"],
+    ["Empty value removes the entry",
+     "Un valor vacío elimina la entrada",
+     "Une valeur vide supprime l'entrée",
+     "Ein leerer Wert entfernt den Eintrag"],
+    ["Empty entry keeps the default: {}",
+     "Una entrada vacía mantiene el valor por defecto: {}",
+     "Une entrée vide conserve la valeur par défaut : {}",
+     "Ein leerer Eintrag behält den Standardwert: {}"],
+    ["Empty entry to finish",
+     "Entrada vacía para terminar",
+     "Entrée vide pour terminer",
+     "Leerer Eintrag zum Beenden"],
+    ["A path is required.",
+     "Se requiere una ruta.",
+     "Un chemin est requis.",
+     "Ein Pfad ist erforderlich."],
+    ["{} does not exist.",
+     "{} no existe.",
+     "{} n'existe pas.",
+     "{} existiert nicht."],
+    ["{} already exists.",
+     "{} ya existe.",
+     "{} existe déjà.",
+     "{} existiert bereits."],
+    ["{} is a directory, a file is required.",
+     "{} es un directorio, se requiere un archivo.",
+     "{} est un répertoire, un fichier est requis.",
+     "{} ist ein Verzeichnis, eine Datei ist erforderlich."],
+    ["{} is not a directory.",
+     "{} no es un directorio.",
+     "{} n'est pas un répertoire.",
+     "{} ist kein Verzeichnis."],
+    ["{} must have one of the extensions: {}",
+     "{} debe tener una de las extensiones: {}",
+     "{} doit avoir l'une des extensions : {}",
+     "{} muss eine dieser Endungen haben: {}"],
+    ["Please confirm the password:",
+     "Confirme la contraseña:",
+     "Veuillez confirmer le mot de passe :",
+     "Bitte das Passwort bestätigen:"],
+    ["The passwords do not match, please try again.",
+     "Las contraseñas no coinciden, inténtelo de nuevo.",
+     "Les mots de passe ne correspondent pas, veuillez réessayer.",
+     "Die Passwörter stimmen nicht überein, bitte erneut versuchen."],
+    ["Requires {}",
+     "Requiere {}",
+     "Requiert {}",
+     "Erfordert {}"],
+    ["Strength: {}. Missing: {}",
+     "Fortaleza: {}. Falta: {}",
+     "Robustesse : {}. Manque : {}",
+     "Stärke: {}. Es fehlt: {}"],
+    ["Strength: {}",
+     "Fortaleza: {}",
+     "Robustesse : {}",
+     "Stärke: {}"],
+    ["at least {} characters",
+     "al menos {} caracteres",
+     "au moins {} caractères",
+     "mindestens {} Zeichen"],
+    ["a lowercase letter",
+     "una letra minúscula",
+     "une lettre minuscule",
+     "ein Kleinbuchstabe"],
+    ["an uppercase letter",
+     "una letra mayúscula",
+     "une lettre majuscule",
+     "ein Großbuchstabe"],
+    ["a digit",
+     "un dígito",
+     "un chiffre",
+     "eine Ziffer"],
+    ["a symbol",
+     "un símbolo",
+     "un symbole",
+     "ein Sonderzeichen"],
+    ["{} bits of entropy",
+     "{} bits de entropía",
+     "{} bits d'entropie",
+     "{} Bit Entropie"],
+    ["{} bits of entropy (has {})",
+     "{} bits de entropía (tiene {})",
+     "{} bits d'entropie (a {})",
+     "{} Bit Entropie (hat {})"],
+    ["not a common password",
+     "no ser una contraseña común",
+     "pas un mot de passe courant",
+     "kein gängiges Passwort"],
+    ["Very weak",
+     "Muy débil",
+     "Très faible",
+     "Sehr schwach"],
+    ["Weak",
+     "Débil",
+     "Faible",
+     "Schwach"],
+    ["Reasonable",
+     "Aceptable",
+     "Correcte",
+     "Ausreichend"],
+    ["Strong",
+     "Fuerte",
+     "Forte",
+     "Stark"],
+    ["Very strong",
+     "Muy fuerte",
+     "Très forte",
+     "Sehr stark"],
+    ["The answer is not valid {}: {}",
+     "La respuesta no es {} válido: {}",
+     "La réponse n'est pas du {} valide : {}",
+     "Die Antwort ist kein gültiges {}: {}"],
+    ["{} is a group heading, pick its options instead",
+     "{} es un encabezado de grupo, elija sus opciones",
+     "{} est un titre de groupe, choisissez plutôt ses options",
+     "{} ist eine Gruppenüberschrift, bitte deren Optionen wählen"],
+    ["unavailable",
+     "no disponible",
+     "indisponible",
+     "nicht verfügbar"],
+    ["{} is not available",
+     "{} no está disponible",
+     "{} n'est pas disponible",
+     "{} ist nicht verfügbar"],
+    ["{} is not available: {}",
+     "{} no está disponible: {}",
+     "{} n'est pas disponible : {}",
+     "{} ist nicht verfügbar: {}"],
+    ["Select all / none",
+     "Seleccionar todo / nada",
+     "Tout / rien sélectionner",
+     "Alle / keine auswählen"],
+    ["At least {} options must be selected",
+     "Se deben seleccionar al menos {} opciones",
+     "Au moins {} options doivent être sélectionnées",
+     "Mindestens {} Optionen müssen ausgewählt sein"],
+    ["At most {} options can be selected",
+     "Se pueden seleccionar como máximo {} opciones",
+     "Au plus {} options peuvent être sélectionnées",
+     "Höchstens {} Optionen können ausgewählt werden"],
+    ["Other…",
+     "Otro…",
+     "Autre…",
+     "Andere…"],
+    ["Please specify:",
+     "Especifique:",
+     "Veuillez préciser :",
+     "Bitte angeben:"],
+    ["A value is required",
+     "Se requiere un valor",
+     "Une valeur est requise",
+     "Ein Wert ist erforderlich"],
+    ["At least {} lines are required",
+     "Se requieren al menos {} líneas",
+     "Au moins {} lignes sont requises",
+     "Mindestens {} Zeilen sind erforderlich"],
+    ["↑↓ to move, enter to select, type to filter",
+     "↑↓ para moverse, enter para elegir, escriba para filtrar",
+     "↑↓ pour se déplacer, entrée pour choisir, tapez pour filtrer",
+     "↑↓ zum Bewegen, Enter zum Auswählen, Tippen zum Filtern"],
+    ["↑↓ to move, space to select one, → to all, ← to none, type to filter",
+     "↑↓ para moverse, espacio para marcar una, → todas, ← ninguna, escriba para filtrar",
+     "↑↓ pour se déplacer, espace pour en cocher une, → toutes, ← aucune, tapez pour filtrer",
+     "↑↓ zum Bewegen, Leertaste wählt eine, → alle, ← keine, Tippen zum Filtern"],
+    ["↑↓ to move, enter to pick the next item in order",
+     "↑↓ para moverse, enter para elegir el siguiente en el orden",
+     "↑↓ pour se déplacer, entrée pour choisir le suivant dans l'ordre",
+     "↑↓ zum Bewegen, Enter wählt den nächsten in der Reihenfolge"],
+    ["{} (item {})",
+     "{} (elemento {})",
+     "{} (élément {})",
+     "{} (Eintrag {})"],
+    ["{} (#{})",
+     "{} (n.º {})",
+     "{} (n° {})",
+     "{} (Nr. {})"],
+    ["A line with only \"{}\" or esc to finish",
+     "Una línea con solo \"{}\" o esc para terminar",
+     "Une ligne contenant seulement \"{}\" ou échap pour terminer",
+     "Eine Zeile nur mit \"{}\" oder Esc zum Beenden"],
+    ["A line with only \"{}\" or esc to finish, {} of {} lines",
+     "Una línea con solo \"{}\" o esc para terminar, {} de {} líneas",
+     "Une ligne contenant seulement \"{}\" ou échap pour terminer, {} sur {} lignes",
+     "Eine Zeile nur mit \"{}\" oder Esc zum Beenden, {} von {} Zeilen"],
+    ["<canceled>",
+     "<cancelado>",
+     "<annulé>",
+     "<abgebrochen>"],
+];
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(src:&str)-> Yaml{
+        YamlLoader::load_from_str(src).unwrap().remove(0)
+    }
+
+    fn mask_ok(mask:&str, val:&str)-> bool{
+        validate_mask(&yaml(&format!("mask: {}", mask)), val).is_ok()
+    }
+
+    #[test]
+    fn ipv4_mask(){
+        for val in ["192.168.0.1", "0.0.0.0", "255.255.255.255"] {
+            assert!(mask_ok("ipv4", val), "{}", val);
+        }
+        for val in ["256.1.1.1", "1.2.3", "1.2.3.4.5", "01.2.3.4", "1.2.3.4 ", "1..2.3", ""] {
+            assert!(mask_ok("ipv4", val) == false, "{}", val);
+        }
+    }
+
+    #[test]
+    fn ipv6_mask(){
+        for val in ["::1", "::", "fe80::1", "2001:db8::8a2e:370:7334", "::ffff:192.0.2.1"] {
+            assert!(mask_ok("ipv6", val), "{}", val);
+        }
+        for val in ["2001:db8:::1", "12345::", "g::1", "1:2:3:4:5:6:7:8:9", "192.168.0.1"] {
+            assert!(mask_ok("ipv6", val) == false, "{}", val);
+        }
+    }
+
+    #[test]
+    fn cidr_mask(){
+        for val in ["10.0.0.0/8", "10.0.0.0/0", "10.0.0.0/32", "2001:db8::/32", "2001:db8::/128"] {
+            assert!(mask_ok("cidr", val), "{}", val);
+        }
+        for val in ["10.0.0.0/33", "2001:db8::/129", "10.0.0.0", "10.0.0.0/", "10.0.0.0/+8", "10.0.0/8", "/8"] {
+            assert!(mask_ok("cidr", val) == false, "{}", val);
+        }
+    }
+
+    #[test]
+    fn mac_mask(){
+        for val in ["00:1a:2b:3c:4d:5e", "00-1A-2B-3C-4D-5E"] {
+            assert!(mask_ok("mac", val), "{}", val);
+        }
+        for val in ["00:1a-2b:3c:4d:5e", "00:1a:2b:3c:4d", "00:1a:2b:3c:4d:5e:6f", "001a2b3c4d5e", "00:1a:2b:3c:4d:5g"] {
+            assert!(mask_ok("mac", val) == false, "{}", val);
+        }
+    }
+
+    #[test]
+    fn semver_mask(){
+        for val in ["1.0.0", "0.10.2", "1.0.0-alpha.1", "1.0.0+build.5", "1.0.0-rc.1+sha.abc"] {
+            assert!(mask_ok("semver", val), "{}", val);
+        }
+        for val in ["01.0.0", "1.0", "1.0.0-", "1.0.0+", "v1.0.0", "1.0.0-alpha..1"] {
+            assert!(mask_ok("semver", val) == false, "{}", val);
+        }
+    }
+
+    #[test]
+    fn port_mask(){
+        for val in ["1", "80", "65535"] {
+            assert!(mask_ok("port", val), "{}", val);
+        }
+        for val in ["0", "65536", "080", "+80", "-1", "8o", ""] {
+            assert!(mask_ok("port", val) == false, "{}", val);
+        }
+    }
+
+    #[test]
+    fn mask_examples(){
+        // the placeholder of every mask is a value it accepts and can be typed
+        for mask in ["ipv4", "ipv6", "cidr", "mac", "hostname", "email", "url", "port", "semver"] {
+            assert!(mask_ok(mask, mask_example(mask)), "{}", mask);
+            assert!(mask_example(mask).chars().all(|c| mask_allows(mask, c)), "{}", mask);
+        }
+        assert!(mask_allows("port", 'a') == false);
+        assert!(mask_allows("ipv4", ':') == false);
+        assert!(mask_allows("ipv6", ':'));
+    }
+
+    fn when(expr:&str)-> bool{
+        let scope = yaml("{env: [prod], features: [a, b], debug: [\"false\"], name: [\"John Doe\"], title: [war and peace], empty: []}");
+        eval_condition(expr, &scope)
+    }
+
+    #[test]
+    fn when_comparisons(){
+        assert!(when("env == prod"));
+        assert!(when("env == 'prod'"));
+        assert!(when("name == \"John Doe\""));
+        assert!(when("env == dev") == false);
+        assert!(when("env != dev"));
+        assert!(when("env != prod") == false);
+        assert!(when("missing != prod"));
+        assert!(when("features contains a"));
+        assert!(when("features contains c") == false);
+        assert!(when("features == a") == false);
+    }
+
+    #[test]
+    fn when_truthiness(){
+        assert!(when("env"));
+        assert!(when("debug") == false);
+        assert!(when("!debug"));
+        assert!(when("empty") == false);
+        assert!(when("missing") == false);
+        assert!(when("!missing"));
+    }
+
+    #[test]
+    fn when_and_or(){
+        assert!(when("env == prod and features contains b"));
+        assert!(when("env == prod and debug") == false);
+        assert!(when("env == dev or features contains a"));
+        assert!(when("env == dev or debug") == false);
+        // and binds tighter than or
+        assert!(when("env == dev and debug or features contains b"));
+        assert!(when("env == prod or debug and missing"));
+        assert!(when("env == dev or debug and env == prod") == false);
+    }
+
+    #[test]
+    fn expand_home(){
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(expand_path("~"), home);
+        assert_eq!(expand_path("~/.ssh/id_rsa"), format!("{}/.ssh/id_rsa", home));
+        // only a leading ~ is the home directory
+        assert_eq!(expand_path("~other/x"), "~other/x");
+        assert_eq!(expand_path("a/~/b"), "a/~/b");
+    }
+
+    #[test]
+    fn expand_variables(){
+        std::env::set_var("INQUIRE_CLI_TEST_DIR", "/srv/app");
+        assert_eq!(expand_path("$INQUIRE_CLI_TEST_DIR/conf"), "/srv/app/conf");
+        assert_eq!(expand_path("${INQUIRE_CLI_TEST_DIR}.d/x"), "/srv/app.d/x");
+        assert_eq!(expand_path("$INQUIRE_CLI_TEST_DIR.bak"), "/srv/app.bak");
+        assert_eq!(expand_path("$INQUIRE_CLI_TEST_DIR_X"), "$INQUIRE_CLI_TEST_DIR_X");
+        assert_eq!(expand_path("${INQUIRE_CLI_TEST_UNSET}/x"), "${INQUIRE_CLI_TEST_UNSET}/x");
+        assert_eq!(expand_path("${INQUIRE_CLI_TEST_DIR"), "${INQUIRE_CLI_TEST_DIR");
+        assert_eq!(expand_path("cost $5 and $"), "cost $5 and $");
+        assert_eq!(expand_path("$/x"), "$/x");
+    }
+
+    #[test]
+    fn output_policies(){
+        let cfg = yaml("{name: pw, type: password, output: omit}");
+        assert_eq!(output_parts(&cfg, yaml("[secret]")), (None, LinkedHashMap::new()));
+
+        // the password hashes its answer itself, output_parts keeps it
+        let cfg = yaml("{name: pw, type: password, output: hash}");
+        assert_eq!(output_parts(&cfg, yaml("[$argon2id$x]")), (Some(yaml("[$argon2id$x]")), LinkedHashMap::new()));
+
+        let cfg = yaml("{name: token, type: text, output: secrets_file, secrets_file: /tmp/s.yml}");
+        let (value, secrets) = output_parts(&cfg, yaml("[abc]"));
+        assert_eq!(value, None);
+        assert_eq!(secrets.get("/tmp/s.yml"), Some(&yaml("[abc]")));
+    }
+
+    #[test]
+    fn group_output_policies(){
+        let cfg = yaml("
+name: users
+type: group
+questions:
+  - {name: user, type: text}
+  - {name: note, type: text, output: omit}
+  - {name: pw, type: password, output: secrets_file, secrets_file: /tmp/s.yml}
+");
+        let (value, secrets) = output_parts(&cfg, yaml("[{user: [a], note: [x], pw: [p1]}, {user: [b], note: [y]}]"));
+        assert_eq!(value, Some(yaml("[{user: [a]}, {user: [b]}]")));
+        assert_eq!(secrets.get("/tmp/s.yml"), Some(&yaml("[{pw: [p1]}, {}]")));
+
+        let single = yaml("{name: users, type: group, repeat: false, questions: [{name: pw, type: password, output: secrets_file, secrets_file: /tmp/s.yml}]}");
+        let (value, secrets) = output_parts(&single, yaml("{pw: [p1]}"));
+        assert_eq!(value, Some(yaml("{}")));
+        assert_eq!(secrets.get("/tmp/s.yml"), Some(&yaml("{pw: [p1]}")));
+    }
+
+    #[test]
+    #[should_panic(expected = "output hash is only supported by password questions")]
+    fn hash_output_needs_password(){
+        check_output_policies(&yaml("[{name: users, type: group, questions: [{name: token, type: text, output: hash}]}]"));
+    }
+
+    #[test]
+    fn parsed_documents_round_trip(){
+        for src in ["[a, b, c]", "{name: app, ports: [80, 443], tls: {enabled: true}}", "hello", "~"] {
+            let val = yaml(src);
+            let text = document_text(&val);
+            assert_eq!(parse_document(&text, "yaml"), Ok(val), "{}", text);
+        }
+        assert_eq!(document_text(&yaml("[a, b]")), "- a\n- b\n");
+    }
+
+    fn format(formatters:&str, val:&str)-> String{
+        format_string(&yaml(&format!("formatters: {}", formatters)), "formatters", val)
+    }
+
+    #[test]
+    fn formatters(){
+        let cases = [
+            ("[{sub_type: trim}]", "  a b  ", "a b"),
+            ("[{sub_type: lowercase}]", "Hello World", "hello world"),
+            ("[{sub_type: uppercase}]", "Hello World", "HELLO WORLD"),
+            ("[{sub_type: collapse_whitespace}]", "  a \t b\n\n c ", "a b c"),
+            ("[{sub_type: slugify}]", "  Hello, World! 2024 ", "hello-world-2024"),
+            ("[{sub_type: slugify}]", "Ünïcode -- über", "ünïcode-über"),
+            ("[{sub_type: slugify}]", "!!!", ""),
+            ("[{sub_type: prefix, value: \"v\"}]", "1.2", "v1.2"),
+            ("[{sub_type: suffix, value: \".com\"}]", "example", "example.com"),
+            ("[{sub_type: regex_replace, value: \"[0-9]+\", replacement: \"#\"}]", "a1b22c", "a#b#c"),
+            ("[{sub_type: regex_replace, value: \"(\\\\w+)@(\\\\w+)\", replacement: \"$2/$1\"}]", "me@host", "host/me"),
+            ("[{sub_type: trim}, {sub_type: slugify}, {sub_type: prefix, value: \"app-\"}]", " My App ", "app-my-app"),
+        ];
+        for (formatters, val, expected) in cases {
+            assert_eq!(format(formatters, val), expected, "{} {}", formatters, val);
+        }
+        assert_eq!(format_string(&yaml("{}"), "formatters", " as is "), " as is ");
+    }
+
+    #[test]
+    fn answers_keep_the_raw_value(){
+        let cfg = yaml("{name: version, type: text, formatters: [{sub_type: prefix, value: v}]}");
+        let first = answered(&cfg, string_array(vec!["1.2".to_string()]));
+        assert_eq!(first.answer, string_array(vec!["v1.2".to_string()]));
+        assert_eq!(first.raw, string_array(vec!["1.2".to_string()]));
+
+        // going back offers the raw answer so accepting it again does not prefix twice
+        let again = with_previous_answer(&cfg, &first.raw);
+        assert_eq!(again["default"].as_str(), Some("1.2"));
+        let second = answered(&again, string_array(vec![again["default"].as_str().unwrap().to_string()]));
+        assert_eq!(second.answer, first.answer);
+
+        let group = yaml("{name: hosts, type: group, questions: [{name: host, type: text, formatters: [{sub_type: suffix, value: .lan}]}]}");
+        let raw = yaml("[{host: [db1]}]");
+        let items = answered(&group, raw.clone());
+        assert_eq!(items.answer, yaml("[{host: [db1.lan]}]"));
+        assert_eq!(with_previous_answer(&group, &items.raw)["default"], raw);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown parse json must be yaml")]
+    fn parse_is_yaml_only(){
+        assert_eq!(editor_rules(&yaml("parse: yaml")).parse.as_deref(), Some("yaml"));
+        editor_rules(&yaml("parse: json"));
+    }
+
+    fn render(template:&str)-> String{
+        std::env::set_var("INQUIRE_CLI_TEST_USER", "deploy");
+        std::env::set_var("INQUIRE_CLI_TEST_SHADOWED", "deploy");
+        let scope = yaml("{db: [pg], tags: [a, b], database: {host: [db1]}, hostname: [answered]}");
+        let answer_conf = yaml("{db: [mysql], region: [eu], env: {INQUIRE_CLI_TEST_SHADOWED: [file]}}");
+        render_template(template, &scope, &answer_conf)
+    }
+
+    #[test]
+    fn template_variables(){
+        // answers so far, then the answers file, then env. and the built-ins
+        assert_eq!(render("{{ db }}://{{region}}"), "pg://eu");
+        assert_eq!(render("{{ tags }} {{ database.host }}"), "a, b db1");
+        assert_eq!(render("{{ env.INQUIRE_CLI_TEST_USER }}"), "deploy");
+        assert_eq!(render("{{ env.INQUIRE_CLI_TEST_SHADOWED }}"), "file");
+        assert_eq!(render("{{ hostname }}"), "answered");
+        assert_eq!(template_variable("hostname", &yaml("{}"), &yaml("{}")), Some(hostname()));
+        assert_eq!(template_variable("env.INQUIRE_CLI_TEST_UNSET", &yaml("{}"), &yaml("{}")), None);
+        assert_eq!(template_variable("missing", &yaml("{}"), &yaml("{}")), None);
+    }
+
+    #[test]
+    fn template_literals(){
+        assert_eq!(render("json {{\"k\": 1}}"), "json {{\"k\": 1}}");
+        assert_eq!(render("{{ unclosed"), "{{ unclosed");
+        assert_eq!(render("{{}} and {{ a b }}"), "{{}} and {{ a b }}");
+        assert_eq!(render("{{{{ db }}}} is {{ db }}"), "{{ db }} is pg");
+        assert_eq!(render("{{{{db}}}}"), "{{db}}");
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown variable \"missing\"")]
+    fn template_unknown_variable(){
+        render("{{ missing }}");
+    }
+
+    #[test]
+    fn when_names(){
+        assert_eq!(condition_names("env == prod and !debug or features contains a"), vec!["env", "debug", "features"]);
+        assert_eq!(condition_names("title == 'war and peace' or name != \"a or b\""), vec!["title", "name"]);
+    }
+
+    #[test]
+    fn when_quoted_keywords(){
+        assert!(when("title == 'war and peace'"));
+        assert!(when("title == \"war and peace\" and env == prod"));
+        assert!(when("title == 'war or peace' or env == dev") == false);
+        assert!(when("title != 'a == b'"));
+        assert!(when("name == 'John Doe' and title contains 'war and peace'"));
+    }
+
+    fn locales(lang:&str)-> Vec<String>{
+        locale_candidates(&Args::parse_from(["inquire-cli", "--lang", lang]))
+    }
+
+    #[test]
+    fn locale_fallbacks(){
+        assert_eq!(locales("pt_BR.UTF-8"), vec!["pt-br", "pt", "en"]);
+        assert_eq!(locales("de_DE@euro"), vec!["de-de", "de", "en"]);
+        assert_eq!(locales("es"), vec!["es", "en"]);
+        assert_eq!(locales("en_US.UTF-8"), vec!["en-us", "en"]);
+        assert_eq!(locales("C"), vec!["en"]);
+        assert_eq!(locales("POSIX"), vec!["en"]);
+        assert_eq!(locales("C.UTF-8"), vec!["en"]);
+    }
+
+    #[test]
+    fn localized_attributes(){
+        let conf = yaml(r#"
+            - name: name
+              message: {en: "Name?", pt: "Nome?", pt_BR: "Seu nome?"}
+              help: {es: "Ayuda", fr: "Aide"}
+              placeholder: {en: "Jane", es: "Juana"}
+              options:
+                - {value: a, label: {en: "First", pt: "Primeiro"}}
+              render_config: {prompt_prefix: {en: "x"}}
+        "#);
+        let q = &localize(&conf, &locales("pt_BR.UTF-8"))[0];
+        assert_eq!(q["message"].as_str(), Some("Seu nome?"));
+        assert_eq!(q["help"].as_str(), Some("Ayuda"));
+        assert_eq!(q["placeholder"].as_str(), Some("Jane"));
+        assert_eq!(q["options"][0]["label"].as_str(), Some("Primeiro"));
+        assert_eq!(q["render_config"], yaml(r#"{prompt_prefix: {en: "x"}}"#));
+
+        let q = &localize(&conf, &locales("pt_PT"))[0];
+        assert_eq!(q["message"].as_str(), Some("Nome?"));
+    }
+}
 
 /*
 
@@ -755,6 +5136,8 @@ RUST_BACKTRACE=1 cargo run -- -o /home/flopes/answers.yml -c '[{"name":"test", "
 
 RUST_BACKTRACE=1 cargo run -- -o /home/flopes/answers.yml -c '[{"name":"test", "type":"text",    "message":"Where are you from?", "suggestions":["Colombia", "Brazil", "Argentina", "USA"] }]'
 
+RUST_BACKTRACE=1 cargo run -- -o /home/flopes/answers.yml -c '[{"name":"test", "type":"path",    "message":"Where is your SSH key?", "path_type":"file", "must_exist":true, "initial_value":"~/.ssh/", "canonicalize":true }]'
+
 RUST_BACKTRACE=1 cargo run -- -o /home/flopes/answers.yml -c '[{"name":"test", "type":"editor",    "message":"Where are you from?", "predefined_text" : "Some predefined text", "editor_command":"vim" }]'
 
 RUST_BACKTRACE=1 cargo run -- -o /home/flopes/answers.yml -c '[{"name":"test", "type":"password",    "message":"Please type the secret password?", "help" : "extra_help", "display_toggle":false, "display_mode":"masked" }]'
@@ -774,6 +5157,28 @@ RUST_BACKTRACE=1 cargo run -- -o /home/flopes/answers.yml -c '[
 
 
 inquire --config='{
+    "title": "", # questionnaire level when the config is a hash with questions
+    # message, help, placeholder, title, description, section, add_message, confirm_message and
+    # option labels can be locale maps {"en": "Name?", "es": "¿Nombre?"}, picked by --lang or LANG
+    "translations": {"es": {"Add another?": "¿Uno más?"}}, # overrides the built-in es, fr, de messages
+    "render_config": { # also per question, merged over the questionnaire one
+        "theme": "default", # default | plain | minimal | vivid, colors are dropped when NO_COLOR is set
+        "prompt_prefix": "?", # glyphs are a string or {"content": "?", "fg": "green", "bold": true}
+        "highlighted_option_prefix": ">",
+        "selected_checkbox": "[x]",
+        "unselected_checkbox": "[ ]",
+        "help_message": {"fg": "cyan", "italic": true}, # styles: fg, bg (name or 0-255), bold, italic
+        "answer": {"fg": "cyan"},
+    },
+    "description": "",
+    "progress": false, # prefix every prompt with "Question n of total"
+    "questions": [],
+}'
+
+inquire --config='{
+    "section": "Database", # prefixes the message
+    "timeout": 30, # seconds without a key press before the default is used, also inside groups,
+                   # the default goes through the validators and post-processing of a typed answer
     "type":"Text" # Text | Editor | DateSelect | Select | MultiSelect | Confirm | CustomType | Password
     "message": "What is your name",
     "render": ?,
@@ -785,46 +5190,139 @@ inquire --config='{
         "params"   : [1]
         }
     ],
-    "formatters": [],
+    "formatters": [ # applied in order to the stored answer, validators see the raw input
+        {"sub_type": "trim"}, # trim | lowercase | uppercase | slugify | collapse_whitespace
+        {"sub_type": "prefix", "value": "https://"}, # prefix | suffix
+        {"sub_type": "regex_replace", "value": "/+$", "replacement": ""},
+    ],
+    "display_formatters": [], # same sub_types, only change how the answer is echoed and reviewed
+    # message, help, default, placeholder, initial_value and predefined_text are templates:
+    # {{ name }} is a previous answer or one from the answers file, {{ env.USER }} an environment variable,
+    # {{ hostname }} the machine hostname, {{{{ name }}}} is a literal {{ name }} and any other {{ is kept as written
+    "when": "db_type == postgres and use_tls", # name | !name | name == value | name != value | name contains value, joined by and / or
     "help": "",
     "auto_complete": [],
     "filter_function": ??
 
 
 
+    # ====== EXCLUSIVE TO CONFIRM ========
+    "yes_words" : ["si", "s"], # accepted answers, the first one is echoed, default y/yes and n/no
+    "no_words" : ["no", "n"],
+    "true_value" : "enabled", # written to the answers file instead of "true" / "false",
+    "false_value" : "disabled", # when and templates still see true / false
+
+    # ====== EXCLUSIVE TO TEXT ========
+    "mask" : "ipv4", # ipv4 | ipv6 | cidr | mac | hostname | email | url | port | semver, only the characters the mask
+                     # allows can be typed, an example is the default placeholder, can not be used with suggestions
+
     # ====== EXCLUSIVE TO Select & MultiSelect ========
     "page_size" : 7, 
-    "options" : [""],
+    "options" : ["", {"value": "red", "label": "Red"}], # the label is shown, the value stored
+    # options sharing a "group" get a heading that can not be picked, a "description" is shown in the help line
+    # while its option is highlighted,
+    # starting_cursor and default indexes count options only
+    # {"value": "pg", "label": "Postgres", "group": "SQL", "description": "relational database"}
+    # "disabled": true or a when expression such as "!cuda", shown with its "reason" but never picked or defaulted
+    "allow_other" : true, # or {"label": "Other…", "message": "Please specify:", "placeholder": "", "mask": "", "validators": []},
+                          # appends an entry asking for free text, stored instead of the option in select, alongside the checked ones in multi_select
     "starting_cursor" : 0,
     "display_option_indicies" : false
     
 
+    # ====== EXCLUSIVE TO RANK ========
+    "options" : ["usb", "disk", "network"],
+    "default" : ["disk", "usb"], # default order, missing options are appended
+    "limit" : 2, # only pick the top N
+
     # ====== EXCLUSIVE TO MULTISELECT ========
     "default_selection" : [""],
     "starting_cursor"   : 0,
     "keep_filter_flag"  : true,
+    "default"           : ["usb", 2], # option values or indexes, or "all"
+    "min"               : 1, # selection count enforced on submit
+    "max"               : 3,
+    "select_all"        : true, # first entry checking every available option, or none when all are checked
 
     # ====== EXCLUSIVE TO DATESELECT ========
     "min_date" : ""  #FOR DATESELECT
     "max_date" : ""  #FOR DATESELECT
     "week_start" : "" # mon | tue | wed | thu | fri | sat | sun
 
+    # ====== EXCLUSIVE TO LIST ========
+    "min_items" : 0,
+    "max_items" : 5,
+    "default" : ["8.8.8.8", "1.1.1.1"],
+
+    # ====== EXCLUSIVE TO MAP ========
+    "key_pattern" : "^[A-Z_][A-Z0-9_]*$",
+    "default" : {"LOG_LEVEL": "info"}, # the previous answers take precedence
+
+    # ====== EXCLUSIVE TO GROUP ========
+    "questions" : [{"name":"host", ...}, {"name":"port", "default":"{{ host }}", "when":"engine == postgres"}],
+    "repeat" : true, # false asks the questions once and writes a single hash
+    "add_message" : "Add another database?",
+    "min_items" : 0,
+    "max_items" : 3,
+    "default" : [{"host": "db1"}], # answers of each item, the previous answers take precedence
+
+    # ====== EXCLUSIVE TO NOTE ========
+    "title" : "Database", # printed in bold before the message
+    "wait" : false, # wait for enter before the next question, skipped when not on a terminal
+
+    # ====== EXCLUSIVE TO COMPUTED ========
+    "value" : "postgres://{{ db_user }}@{{ db_host }}:{{ db_port }}/{{ db_name }}", # template
+    "expression" : "db_type == postgres and use_tls", # or a when style expression, stored as true / false
+
+    # ====== EXCLUSIVE TO PATH ========
+    "path_type" : "any", # file | dir | any
+    "extensions" : ["pem", "pub"],
+    "must_exist" : false,
+    "must_not_exist" : false,
+    "canonicalize" : false, # store the absolute path
+
+    # ====== EXCLUSIVE TO MULTILINE ========
+    "terminator" : ".", # a line with only this ends the input, esc works too
+    "min_lines" : 1,
+    "max_lines" : 10,
+    "predefined_text": "",
+
     # ====== EXCLUSIVE TO EDITOR ========
     "editor_args" : ["nano"],
     "file_extension" : "",
     "predefined_text": "",
+    "predefined_file": "", # template file used to seed the buffer
+    "strip_comments": false, # drop instruction lines after editing
+    "comment_prefix": "#",
+    "parse": "", # yaml, store the edited document as a structured value
 
 
     # ====== EXCLUSIVE TO PASSWORD ========
     display_mode: "Hidden", # Hidden|Masked|Full
     mask_character: "*",
     toggle_display: false,
+    confirm: false, # ask twice and re-prompt on mismatch
+    confirm_message: "Please confirm the password:",
+    output: "plaintext", # plaintext | omit | hash | secrets_file, every question but hash which is password only
+    hash_algorithm: "argon2", # argon2 | bcrypt | sha512_crypt
+    secrets_file: "", # 0600 yaml file the answer is written to when output is secrets_file
+                      # inside a group it is stored under the group name, one hash per item
+    policy: { # the strength and missing rules are shown below the prompt while typing
+        min_length: 12,
+        lowercase: true,
+        uppercase: true,
+        digit: true,
+        symbol: true,
+        min_entropy: 60,
+        deny_list_file: "", # one password per line
+    },
 
 
     validators:[{
         type: string      
         sub_type: regex_match | min_len | max_len | file_exist | dir_exists | dir_of_file_exists
         value: 1
+        message: "" # optional, replaces the built-in error message
     }
     ]
     validators:[{